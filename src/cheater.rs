@@ -0,0 +1,135 @@
+// Heurísticas para apontar possíveis cheaters. Cada regra pode ser desativada e ajustada nas
+// configurações, e o jogador marcado guarda a descrição das regras que dispararam.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{player::Player, stats::StatsType, util};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheaterRule {
+    // Conta nova com FKDR alto.
+    NewAccount,
+    // Winstreak enorme com poucas horas jogadas.
+    Winstreak,
+    // FKDR subiu muito desde o último snapshot.
+    StatJump,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RuleParameter {
+    NewAccountDays,
+    NewAccountFkdr,
+    WinstreakMin,
+    WinstreakMaxHours,
+    StatJumpFkdr,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheaterRules {
+    pub new_account: bool,
+    pub new_account_days: f64,
+    pub new_account_fkdr: f64,
+    pub winstreak: bool,
+    pub winstreak_min: f64,
+    pub winstreak_max_hours: f64,
+    pub stat_jump: bool,
+    pub stat_jump_fkdr: f64,
+}
+
+impl Default for CheaterRules {
+    fn default() -> Self {
+        CheaterRules {
+            new_account: true,
+            new_account_days: 7.,
+            new_account_fkdr: 5.,
+            winstreak: true,
+            winstreak_min: 30.,
+            winstreak_max_hours: 10.,
+            stat_jump: true,
+            stat_jump_fkdr: 2.,
+        }
+    }
+}
+
+impl CheaterRules {
+    pub fn set_enabled(&mut self, rule: CheaterRule, enabled: bool) {
+        match rule {
+            CheaterRule::NewAccount => self.new_account = enabled,
+            CheaterRule::Winstreak => self.winstreak = enabled,
+            CheaterRule::StatJump => self.stat_jump = enabled,
+        }
+    }
+
+    pub fn set_parameter(&mut self, parameter: RuleParameter, value: f64) {
+        match parameter {
+            RuleParameter::NewAccountDays => self.new_account_days = value,
+            RuleParameter::NewAccountFkdr => self.new_account_fkdr = value,
+            RuleParameter::WinstreakMin => self.winstreak_min = value,
+            RuleParameter::WinstreakMaxHours => self.winstreak_max_hours = value,
+            RuleParameter::StatJumpFkdr => self.stat_jump_fkdr = value,
+        }
+    }
+
+    // Descrição de cada regra que disparou. Cada regra aparece no máximo uma vez, no primeiro
+    // modo em que disparou.
+    pub fn evaluate(&self, player: &Player) -> Vec<String> {
+        let mut flags = vec![];
+        let now = chrono::Utc::now().timestamp_millis();
+        let account_age_days = (now - player.account_creation) as f64 / 86_400_000.;
+
+        let mut new_account = self.new_account;
+        let mut winstreak = self.winstreak;
+        let mut stat_jump = self.stat_jump;
+
+        for stats_type in StatsType::get_stats_list() {
+            let Some(stats) = player.stats(&stats_type) else {
+                continue;
+            };
+            let fkdr = stats.value("final_kill_death_ratio");
+
+            if let (true, Some(fkdr)) = (new_account, fkdr) {
+                if account_age_days < self.new_account_days && fkdr >= self.new_account_fkdr {
+                    flags.push(format!(
+                        "Conta criada há {:.0} dias com FKDR {:.2} ({stats_type})",
+                        account_age_days.floor(),
+                        fkdr
+                    ));
+                    new_account = false;
+                }
+            }
+
+            let current_winstreak = stats.value("winstreak");
+            let hours_played = stats.value("hours_played");
+            if let (true, Some(current_winstreak), Some(hours_played)) =
+                (winstreak, current_winstreak, hours_played)
+            {
+                if current_winstreak >= self.winstreak_min
+                    && hours_played <= self.winstreak_max_hours
+                {
+                    flags.push(format!(
+                        "Winstreak de {current_winstreak:.0} com {hours_played:.0} horas jogadas ({stats_type})"
+                    ));
+                    winstreak = false;
+                }
+            }
+
+            let last_snapshot = player.last_snapshots.get(stats_type.id());
+            let previous_fkdr =
+                last_snapshot.and_then(|snapshot| snapshot.values.get("final_kill_death_ratio"));
+            if let (true, Some(fkdr), Some(previous_fkdr), Some(snapshot)) =
+                (stat_jump, fkdr, previous_fkdr, last_snapshot)
+            {
+                if fkdr - previous_fkdr >= self.stat_jump_fkdr {
+                    flags.push(format!(
+                        "FKDR subiu de {previous_fkdr:.2} para {fkdr:.2} desde {} ({stats_type})",
+                        util::unix_time_to_date(snapshot.taken_at)
+                    ));
+                    stat_jump = false;
+                }
+            }
+        }
+
+        flags
+    }
+}
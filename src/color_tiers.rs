@@ -0,0 +1,119 @@
+// Cores dos valores de stats: cada stat tem 5 limites que separam 6 faixas de cor.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::util::Rgb;
+
+// Cinza, branco, amarelo, laranja, vermelho e roxo, nessa ordem.
+pub fn tier_colors() -> [Rgb; 6] {
+    [
+        Rgb::new(170, 170, 170),
+        Rgb::new(255, 255, 255),
+        Rgb::new(255, 255, 85),
+        Rgb::new(255, 170, 0),
+        Rgb::new(255, 85, 85),
+        Rgb::new(255, 85, 255),
+    ]
+}
+
+// Valor abaixo do primeiro limite fica cinza, acima do último fica roxo. Os limites precisam
+// estar em ordem crescente, ver `is_ascending`.
+pub fn tier_color(value: f64, thresholds: &[f64; 5]) -> Rgb {
+    let tier = thresholds
+        .iter()
+        .take_while(|threshold| value >= **threshold)
+        .count();
+    tier_colors()[tier].clone()
+}
+
+fn is_ascending(thresholds: &[f64]) -> bool {
+    thresholds.windows(2).all(|pair| pair[0] <= pair[1])
+}
+
+#[derive(Debug, Clone)]
+pub struct StatTiers {
+    // Nome do stat usado em `Stats::value`.
+    pub stat: String,
+    pub name: String,
+    pub thresholds: [f64; 5],
+}
+
+// Salvo no config como um objeto com os 5 limites de cada stat.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "Value", into = "Value")]
+pub struct ColorTiers {
+    pub stats: Vec<StatTiers>,
+}
+
+impl Default for ColorTiers {
+    fn default() -> Self {
+        let tiers = |stat: &str, name: &str, thresholds: [f64; 5]| StatTiers {
+            stat: stat.to_string(),
+            name: name.to_string(),
+            thresholds,
+        };
+        ColorTiers {
+            stats: vec![
+                tiers("winrate", "WLR", [0.5, 1., 2., 4., 8.]),
+                tiers("final_kill_death_ratio", "FKDR", [1., 2., 4., 8., 15.]),
+                tiers("kill_death_ratio", "KDR", [0.5, 1., 2., 4., 8.]),
+                tiers("winstreak", "Winstreak", [5., 10., 25., 50., 100.]),
+            ],
+        }
+    }
+}
+
+// Limites ausentes ou inválidos no config ficam com o valor padrão.
+impl From<Value> for ColorTiers {
+    fn from(config: Value) -> Self {
+        let mut color_tiers = ColorTiers::default();
+        for stat_tiers in &mut color_tiers.stats {
+            let Some(values) = config[&stat_tiers.stat].as_array() else {
+                continue;
+            };
+            let thresholds: Vec<f64> = values.iter().filter_map(Value::as_f64).collect();
+            if let Ok(thresholds) = <[f64; 5]>::try_from(thresholds) {
+                if is_ascending(&thresholds) {
+                    stat_tiers.thresholds = thresholds;
+                }
+            }
+        }
+        color_tiers
+    }
+}
+
+impl From<ColorTiers> for Value {
+    fn from(color_tiers: ColorTiers) -> Self {
+        let map: Map<String, Value> = color_tiers
+            .stats
+            .iter()
+            .map(|stat_tiers| (stat_tiers.stat.clone(), json!(stat_tiers.thresholds)))
+            .collect();
+        Value::Object(map)
+    }
+}
+
+impl ColorTiers {
+    // Retorna false, sem mudar nada, se o valor deixaria os limites fora de ordem.
+    pub fn set_threshold(&mut self, stat: &str, index: usize, value: f64) -> bool {
+        let Some(stat_tiers) = self.stats.iter_mut().find(|x| x.stat == stat) else {
+            return false;
+        };
+        let mut thresholds = stat_tiers.thresholds;
+        thresholds[index] = value;
+        if !value.is_finite() || !is_ascending(&thresholds) {
+            return false;
+        }
+        stat_tiers.thresholds = thresholds;
+        true
+    }
+
+    // Stats sem faixas configuradas ficam brancos.
+    pub fn color(&self, stat: &str, value: f64) -> Rgb {
+        match self.stats.iter().find(|x| x.stat == stat) {
+            Some(stat_tiers) => tier_color(value, &stat_tiers.thresholds),
+            None => Rgb::new(255, 255, 255),
+        }
+    }
+}
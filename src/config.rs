@@ -1,117 +1,405 @@
-use std::{
-    fs::{self, File, OpenOptions},
-    io::Write,
-    path::Path,
-};
+use std::{fs, path::Path};
 
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::{
+    cheater::CheaterRules, color_tiers::ColorTiers, paths, snapshots::SnapshotRetention,
+    threat::ThreatSettings,
+};
+
+// Versão atual do formato do arquivo. Arquivos sem o campo `version` são da versão 0.
+pub const CONFIG_VERSION: u64 = 1;
+
+// Cada migração atualiza o config da versão igual ao seu índice para a seguinte.
+const MIGRATIONS: [fn(&mut Value); CONFIG_VERSION as usize] = [migrate_v0_to_v1];
+
 pub fn get_config_file_path() -> String {
-    format!(
-        "{}/kc_overlay_config.json",
-        super::util::get_minecraft_dir()
-    )
+    paths::config_file()
 }
 
-pub fn get_config() -> Value {
-    super::util::get_json(get_config_file_path())
+// Arquivo opcional com modos de stats extras ou que substituem os padrões.
+pub fn get_modes_file_path() -> String {
+    format!("{}/kc_overlay_modes.json", paths::config_dir())
 }
 
-pub fn check_config_file() -> bool {
-    let file_exists = Path::new(&get_config_file_path()).exists();
-    let mut conf_json = match file_exists {
-        true => super::util::get_json(get_config_file_path()),
-        false => {
-            let minecraft_path = super::util::get_minecraft_dir();
-            if !Path::new(&minecraft_path).exists() {
-                fs::create_dir_all(minecraft_path).unwrap()
-            }
-            serde_json::json!({})
-        }
-    };
+// Cópia do último config lido sem erros.
+fn get_backup_file_path() -> String {
+    format!("{}.bak", get_config_file_path())
+}
 
-    let mut file = File::create(get_config_file_path()).unwrap();
+pub fn config_file_exists() -> bool {
+    Path::new(&get_config_file_path()).exists()
+}
 
-    if let Value::Object(map) = &mut conf_json {
-        if !map.contains_key("client") {
-            map.insert("client".to_owned(), serde_json::to_value(0).unwrap());
-        }
-        if !map.contains_key("custom_client_path") {
-            map.insert(
-                "custom_client_path".to_owned(),
-                serde_json::to_value("").unwrap(),
-            );
+// Configurações salvas. Campos ausentes ou inválidos no arquivo ficam com o valor padrão.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "Value")]
+pub struct Config {
+    pub version: u64,
+    // Id do client, ex: "lunar". Ver `MineClient::id`.
+    pub client: String,
+    pub custom_client_path: String,
+    pub never_minimize: bool,
+    pub seconds_to_minimize: u64,
+    pub auto_manage_players: bool,
+    // Id do modo, ex: "bedwars_all".
+    pub stats_type: String,
+    pub window_scale: f64,
+    pub max_retries: u32,
+    pub request_timeout: u64,
+    pub connect_timeout: u64,
+    // Ex: "http://127.0.0.1:8080" ou "socks5://127.0.0.1:1080". Vazio não usa proxy.
+    pub proxy: String,
+    pub ca_certificate_path: String,
+    pub max_concurrent_requests: usize,
+    pub threat: ThreatSettings,
+    pub color_tiers: ColorTiers,
+    pub extra_columns: Vec<String>,
+    pub snapshot_retention: SnapshotRetention,
+    pub show_deltas: bool,
+    // Jogador acompanhado na tela de sessão.
+    pub username: String,
+    pub cheater_rules: CheaterRules,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            version: CONFIG_VERSION,
+            client: "default".to_string(),
+            custom_client_path: String::new(),
+            never_minimize: false,
+            seconds_to_minimize: 12,
+            auto_manage_players: true,
+            stats_type: "bedwars_all".to_string(),
+            window_scale: 1.,
+            max_retries: 3,
+            request_timeout: 8,
+            connect_timeout: 5,
+            proxy: String::new(),
+            ca_certificate_path: String::new(),
+            max_concurrent_requests: 6,
+            threat: ThreatSettings::default(),
+            color_tiers: ColorTiers::default(),
+            extra_columns: vec![],
+            snapshot_retention: SnapshotRetention::default(),
+            show_deltas: true,
+            username: String::new(),
+            cheater_rules: CheaterRules::default(),
         }
-        if !map.contains_key("never_minimize") {
-            map.insert(
-                "never_minimize".to_owned(),
-                serde_json::to_value(false).unwrap(),
-            );
+    }
+}
+
+// Lê cada campo separadamente, assim um valor com tipo errado não descarta o arquivo inteiro.
+impl From<Value> for Config {
+    fn from(value: Value) -> Self {
+        let mut config = Config::default();
+        field(&value, "version", &mut config.version);
+        field(&value, "client", &mut config.client);
+        field(&value, "custom_client_path", &mut config.custom_client_path);
+        field(&value, "never_minimize", &mut config.never_minimize);
+        field(
+            &value,
+            "seconds_to_minimize",
+            &mut config.seconds_to_minimize,
+        );
+        field(
+            &value,
+            "auto_manage_players",
+            &mut config.auto_manage_players,
+        );
+        field(&value, "stats_type", &mut config.stats_type);
+        field(&value, "window_scale", &mut config.window_scale);
+        field(&value, "max_retries", &mut config.max_retries);
+        field(&value, "request_timeout", &mut config.request_timeout);
+        field(&value, "connect_timeout", &mut config.connect_timeout);
+        field(&value, "proxy", &mut config.proxy);
+        field(
+            &value,
+            "ca_certificate_path",
+            &mut config.ca_certificate_path,
+        );
+        field(
+            &value,
+            "max_concurrent_requests",
+            &mut config.max_concurrent_requests,
+        );
+        field(&value, "threat", &mut config.threat);
+        field(&value, "color_tiers", &mut config.color_tiers);
+        field(&value, "extra_columns", &mut config.extra_columns);
+        field(&value, "snapshot_retention", &mut config.snapshot_retention);
+        field(&value, "show_deltas", &mut config.show_deltas);
+        field(&value, "username", &mut config.username);
+        field(&value, "cheater_rules", &mut config.cheater_rules);
+        config
+    }
+}
+
+fn field<T: DeserializeOwned>(config: &Value, name: &str, target: &mut T) {
+    let Some(value) = config.get(name) else {
+        return;
+    };
+    match serde_json::from_value(value.clone()) {
+        Ok(value) => *target = value,
+        Err(e) => println!("Invalid config field {name}: {e}"),
+    }
+}
+
+impl Config {
+    // Lê o arquivo de configuração, uma vez ao abrir o overlay. Se estiver inválido, usa o backup
+    // ou os padrões e retorna um aviso para mostrar ao usuário.
+    pub fn load() -> (Self, Option<String>) {
+        let path = get_config_file_path();
+        if !Path::new(&path).exists() {
+            return (Config::default(), None);
         }
-        if !map.contains_key("seconds_to_minimize") {
-            map.insert(
-                "seconds_to_minimize".to_owned(),
-                serde_json::to_value(12).unwrap(),
-            );
+
+        let error = match read(&path) {
+            Ok(config) => {
+                let warning = config.is_newer().then(|| {
+                    "O arquivo de configuração é de uma versão mais nova do KC Overlay. As mudanças não serão salvas.".to_string()
+                });
+                return (config, warning);
+            }
+            Err(e) => e,
+        };
+        println!("Invalid config file: {error}");
+
+        // O próximo save substitui o arquivo, então guarda o inválido para não perder edições.
+        if let Err(e) = fs::copy(&path, format!("{path}.invalid")) {
+            println!("Failed to keep invalid config file: {e}");
         }
-        if !map.contains_key("auto_manage_players") {
-            map.insert(
-                "auto_manage_players".to_owned(),
-                serde_json::to_value(true).unwrap(),
-            );
+
+        match read(&get_backup_file_path()) {
+            Ok(config) => (
+                config,
+                Some(format!(
+                    "O arquivo de configuração estava inválido ({error}). O último backup foi restaurado."
+                )),
+            ),
+            Err(_) => (
+                Config::default(),
+                Some(format!(
+                    "O arquivo de configuração estava inválido ({error}). As configurações padrão foram restauradas."
+                )),
+            ),
         }
-        if !map.contains_key("stats_type") {
-            map.insert(
-                "stats_type".to_owned(),
-                serde_json::to_value("Bedwars Geral").unwrap(),
-            );
+    }
+
+    // Arquivos de versões mais novas não são salvos, assim não perdem campos que esta versão não
+    // conhece.
+    pub fn save(&self) {
+        if self.is_newer() {
+            println!("Not saving config file version {}.", self.version);
+            return;
         }
-        if !map.contains_key("window_scale") {
-            map.insert(
-                "window_scale".to_owned(),
-                serde_json::to_value(1.0).unwrap(),
-            );
+        let content = serde_json::to_string_pretty(self).unwrap();
+        if let Err(e) = super::util::write_atomic(&get_config_file_path(), &content) {
+            println!("Failed to save config file: {e}");
         }
     }
 
-    let serializedjson = serde_json::to_string_pretty(&conf_json).unwrap();
+    fn is_newer(&self) -> bool {
+        self.version > CONFIG_VERSION
+    }
 
-    file.write_all(serializedjson.as_bytes()).unwrap();
+    // Corrige valores fora dos limites aceitos pelas configurações.
+    fn validated(mut self) -> Self {
+        self.window_scale = if self.window_scale.is_finite() {
+            self.window_scale.clamp(0.5, 1.25)
+        } else {
+            1.
+        };
+        self.seconds_to_minimize = self.seconds_to_minimize.clamp(5, 120);
+        self.request_timeout = self.request_timeout.max(1);
+        self.connect_timeout = self.connect_timeout.max(1);
+        self.max_concurrent_requests = self.max_concurrent_requests.max(1);
+        self
+    }
+}
+
+// Guarda uma cópia do arquivo atual. Chamado ao abrir o overlay, depois de uma leitura sem erros.
+pub fn back_up() {
+    let path = get_config_file_path();
+    if !Path::new(&path).exists() {
+        return;
+    }
+    if let Err(e) = fs::copy(&path, get_backup_file_path()) {
+        println!("Failed to back up config file: {e}");
+    }
+}
+
+fn read(path: &str) -> Result<Config, String> {
+    let mut config = super::util::get_json(path)?;
+    if !config.is_object() {
+        return Err("expected a JSON object".to_string());
+    }
+    migrate(&mut config);
+    Ok(Config::from(config).validated())
+}
+
+// Atualiza um config de qualquer versão anterior para a atual.
+fn migrate(config: &mut Value) {
+    let version = config["version"].as_u64().unwrap_or(0);
+    if version > CONFIG_VERSION {
+        println!("Config file version {version} is newer than {CONFIG_VERSION}.");
+        return;
+    }
+
+    for migration in &MIGRATIONS[version as usize..] {
+        migration(config);
+    }
+    config["version"] = CONFIG_VERSION.into();
+}
+
+// Nomes dos modos padrão na versão 0. Nomes de modos do arquivo do usuário ficam como estão e
+// viram id ao abrir o overlay, já que `StatsType::from_string` também aceita o nome.
+const V0_MODE_NAMES: [(&str, &str); 12] = [
+    ("Bedwars Geral", "bedwars_all"),
+    ("Bedwars Solo", "bedwars_solo"),
+    ("Bedwars Duplas", "bedwars_doubles"),
+    ("Bedwars Trios", "bedwars_trios"),
+    ("Bedwars Quartetos", "bedwars_quads"),
+    ("Skywars Solo", "skywars_solo"),
+    ("Skywars Time", "skywars_team"),
+    ("Duels Geral", "duels_all"),
+    ("Duels Sumo", "duels_sumo"),
+    ("Duels UHC", "duels_uhc"),
+    ("Duels NoDebuff", "duels_nodebuff"),
+    ("Duels Gladiator", "duels_gladiator"),
+];
+
+// Client salvo como número e modo salvo pelo nome mostrado na interface viram ids.
+fn migrate_v0_to_v1(config: &mut Value) {
+    if let Some(client) = config["client"].as_i64() {
+        let client = match client {
+            1 => "badlion",
+            2 => "lunar",
+            3 => "legacy_launcher",
+            4 => "custom",
+            5 => "silent",
+            _ => "default",
+        };
+        config["client"] = client.into();
+    }
 
-    !file_exists
+    if let Some(stats_type) = config["stats_type"].as_str() {
+        if let Some((_, id)) = V0_MODE_NAMES.iter().find(|(name, _)| *name == stats_type) {
+            config["stats_type"] = (*id).into();
+        }
+    }
 }
 
-pub fn save_settings(
-    never_minimize: Option<bool>,
-    seconds_to_minimize: Option<u64>,
-    auto_manage_players: Option<bool>,
-    stats_type: Option<String>,
-    window_scale: Option<f64>,
-) {
-    let mut config = get_config();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if let Some(never_minimize_option) = never_minimize {
-        config["never_minimize"] = serde_json::json!(never_minimize_option)
+    // Escreve o JSON em um arquivo temporário e lê como o overlay leria.
+    fn read_json(name: &str, json: &str) -> Config {
+        let path = std::env::temp_dir().join(format!(
+            "kc_overlay_config_test_{}_{name}.json",
+            std::process::id()
+        ));
+        fs::write(&path, json).unwrap();
+        let config = read(&path.to_string_lossy());
+        fs::remove_file(&path).unwrap();
+        config.unwrap()
     }
-    if let Some(seconds) = seconds_to_minimize {
-        config["seconds_to_minimize"] = serde_json::json!(seconds)
+
+    #[test]
+    fn migrates_v0_client_numbers() {
+        let clients = [
+            (0, "default"),
+            (1, "badlion"),
+            (2, "lunar"),
+            (3, "legacy_launcher"),
+            (4, "custom"),
+            (5, "silent"),
+        ];
+        for (number, id) in clients {
+            let config = read_json("client", &format!(r#"{{"client": {number}}}"#));
+            assert_eq!(config.client, id);
+            assert_eq!(config.version, CONFIG_VERSION);
+        }
+
+        let config = read_json("client_out_of_range", r#"{"client": 9}"#);
+        assert_eq!(config.client, "default");
     }
-    if let Some(auto_manage_players_option) = auto_manage_players {
-        config["auto_manage_players"] = serde_json::json!(auto_manage_players_option)
+
+    #[test]
+    fn migrates_v0_mode_names() {
+        // Nomes que as versões anteriores salvavam.
+        let modes = [
+            ("Bedwars Geral", "bedwars_all"),
+            ("Bedwars Solo", "bedwars_solo"),
+            ("Bedwars Duplas", "bedwars_doubles"),
+            ("Bedwars Trios", "bedwars_trios"),
+            ("Bedwars Quartetos", "bedwars_quads"),
+            ("Skywars Solo", "skywars_solo"),
+            ("Skywars Time", "skywars_team"),
+            ("Duels Geral", "duels_all"),
+            ("Duels Sumo", "duels_sumo"),
+            ("Duels UHC", "duels_uhc"),
+            ("Duels NoDebuff", "duels_nodebuff"),
+            ("Duels Gladiator", "duels_gladiator"),
+        ];
+        for (name, id) in modes {
+            let config = read_json("mode", &format!(r#"{{"stats_type": "{name}"}}"#));
+            assert_eq!(config.stats_type, id);
+        }
     }
-    if let Some(stats_type_option) = stats_type {
-        config["stats_type"] = serde_json::json!(stats_type_option)
+
+    #[test]
+    fn keeps_unknown_v0_mode_name() {
+        let config = read_json("unknown_mode", r#"{"stats_type": "Modo Inexistente"}"#);
+        assert_eq!(config.stats_type, "Modo Inexistente");
+    }
+
+    #[test]
+    fn treats_missing_version_as_v0() {
+        let config = read_json(
+            "no_version",
+            r#"{"client": 2, "stats_type": "Duels Sumo", "never_minimize": true}"#,
+        );
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.client, "lunar");
+        assert_eq!(config.stats_type, "duels_sumo");
+        assert!(config.never_minimize);
     }
-    if let Some(scale) = window_scale {
-        config["window_scale"] = serde_json::json!(scale)
+
+    #[test]
+    fn keeps_v1_config() {
+        let config = read_json(
+            "v1",
+            r#"{"version": 1, "client": "silent", "stats_type": "skywars_team"}"#,
+        );
+        assert_eq!(config.version, 1);
+        assert_eq!(config.client, "silent");
+        assert_eq!(config.stats_type, "skywars_team");
     }
 
-    let mut config_file = OpenOptions::new()
-        .write(true)
-        .truncate(true)
-        .open(get_config_file_path())
-        .unwrap();
-    config_file
-        .write_all(serde_json::to_string_pretty(&config).unwrap().as_bytes())
-        .unwrap();
+    #[test]
+    fn keeps_newer_config_unmigrated() {
+        let version = CONFIG_VERSION + 1;
+        let config = read_json(
+            "newer",
+            &format!(r#"{{"version": {version}, "client": 2, "stats_type": "Bedwars Solo"}}"#),
+        );
+        assert_eq!(config.version, version);
+        assert!(config.is_newer());
+        // Sem migração, os campos com outro tipo ficam com o padrão.
+        assert_eq!(config.client, "default");
+        assert_eq!(config.stats_type, "Bedwars Solo");
+    }
+
+    #[test]
+    fn keeps_valid_fields_when_one_is_invalid() {
+        let config = read_json(
+            "invalid_field",
+            r#"{"version": 1, "client": "lunar", "window_scale": "grande"}"#,
+        );
+        assert_eq!(config.client, "lunar");
+        assert_eq!(config.window_scale, 1.);
+    }
 }
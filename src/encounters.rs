@@ -0,0 +1,110 @@
+// Encontros: cada vez que um jogador aparece em uma sala com o usuário. Ficam no mesmo banco do
+// histórico de stats. Os logs não dizem o time de cada jogador, então só dá para saber que era um
+// adversário quando um de nós matou o outro.
+
+use rusqlite::{params, Connection};
+
+use crate::snapshots;
+
+#[derive(Debug, Clone)]
+pub struct Encounter {
+    pub at: i64,
+    // Nome que o jogador usava no encontro.
+    pub username: String,
+    // Id do modo selecionado quando o encontro aconteceu.
+    pub mode: String,
+    // None quando não dá para saber pelos logs.
+    pub same_team: Option<bool>,
+    // Resultado da partida do usuário, quando a sessão conseguiu identificar.
+    pub won: Option<bool>,
+}
+
+// Chamado por `snapshots` ao abrir o banco.
+pub fn create_table(connection: &Connection) -> rusqlite::Result<()> {
+    connection.execute_batch(
+        "CREATE TABLE IF NOT EXISTS encounters (
+            id INTEGER PRIMARY KEY,
+            uuid TEXT NOT NULL,
+            username TEXT NOT NULL,
+            lobby_at INTEGER NOT NULL,
+            mode TEXT NOT NULL,
+            same_team INTEGER,
+            won INTEGER,
+            UNIQUE (uuid, lobby_at)
+        );",
+    )?;
+
+    // Bancos criados enquanto o encontro não guardava o time não têm a coluna.
+    if connection
+        .prepare("SELECT same_team FROM encounters LIMIT 0")
+        .is_err()
+    {
+        connection.execute("ALTER TABLE encounters ADD COLUMN same_team INTEGER", [])?;
+    }
+    Ok(())
+}
+
+// Registra o jogador na sala iniciada em `lobby_at`. Entrar de novo na mesma sala não conta.
+pub fn record(uuid: &str, username: &str, lobby_at: i64, mode: &str) {
+    let result = snapshots::with_connection(|connection| {
+        connection.execute(
+            "INSERT OR IGNORE INTO encounters (uuid, username, lobby_at, mode)
+            VALUES (?1, ?2, ?3, ?4)",
+            params![uuid, username, lobby_at, mode],
+        )
+    });
+    if let Err(e) = result {
+        println!("Failed to save encounter: {e}");
+    }
+}
+
+// Salva o resultado da partida em todos os encontros da sala que ainda não têm resultado.
+pub fn set_result(lobby_at: i64, won: bool) {
+    let result = snapshots::with_connection(|connection| {
+        connection.execute(
+            "UPDATE encounters SET won = ?1 WHERE lobby_at = ?2 AND won IS NULL",
+            params![won, lobby_at],
+        )
+    });
+    if let Err(e) = result {
+        println!("Failed to save match result: {e}");
+    }
+}
+
+// Marca o jogador como adversário na sala iniciada em `lobby_at`.
+pub fn set_opponent(uuid: &str, lobby_at: i64) {
+    let result = snapshots::with_connection(|connection| {
+        connection.execute(
+            "UPDATE encounters SET same_team = 0 WHERE uuid = ?1 AND lobby_at = ?2",
+            params![uuid, lobby_at],
+        )
+    });
+    if let Err(e) = result {
+        println!("Failed to save opponent: {e}");
+    }
+}
+
+// Encontros com o jogador, do mais recente para o mais antigo.
+pub fn list(uuid: &str) -> Vec<Encounter> {
+    let result = snapshots::with_connection(|connection| {
+        let mut statement = connection.prepare(
+            "SELECT lobby_at, username, mode, same_team, won FROM encounters WHERE uuid = ?1
+            ORDER BY lobby_at DESC",
+        )?;
+        let rows = statement.query_map(params![uuid], |row| {
+            Ok(Encounter {
+                at: row.get(0)?,
+                username: row.get(1)?,
+                mode: row.get(2)?,
+                same_team: row.get(3)?,
+                won: row.get(4)?,
+            })
+        })?;
+        rows.collect()
+    });
+
+    result.unwrap_or_else(|e| {
+        println!("Failed to read encounters: {e}");
+        vec![]
+    })
+}
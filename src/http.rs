@@ -0,0 +1,44 @@
+// Cliente HTTP compartilhado por todo o programa (consultas à API do Mush e updates).
+
+use std::{fs, time::Duration};
+
+use reqwest::{Certificate, Client, Proxy};
+
+use crate::config::Config;
+
+pub fn build_client(config: &Config) -> Client {
+    let mut builder = Client::builder()
+        .user_agent(format!("KC-Overlay/{}", env!("CARGO_PKG_VERSION")))
+        .connect_timeout(Duration::from_secs(config.connect_timeout))
+        .timeout(Duration::from_secs(config.request_timeout))
+        .pool_idle_timeout(Duration::from_secs(90));
+
+    // Proxy opcional, ex: "http://127.0.0.1:8080" ou "socks5://127.0.0.1:1080".
+    let proxy = &config.proxy;
+    if !proxy.is_empty() {
+        match Proxy::all(proxy) {
+            Ok(proxy) => builder = builder.proxy(proxy),
+            Err(e) => println!("Invalid proxy {proxy}: {e}"),
+        }
+    }
+
+    // Certificado extra, para redes que interceptam HTTPS.
+    let ca_certificate_path = &config.ca_certificate_path;
+    if !ca_certificate_path.is_empty() {
+        match fs::read(ca_certificate_path)
+            .map_err(|e| e.to_string())
+            .and_then(|pem| Certificate::from_pem(&pem).map_err(|e| e.to_string()))
+        {
+            Ok(certificate) => builder = builder.add_root_certificate(certificate),
+            Err(e) => println!("Failed to load certificate {ca_certificate_path}: {e}"),
+        }
+    }
+
+    match builder.build() {
+        Ok(client) => client,
+        Err(e) => {
+            println!("Failed to build HTTP client: {e}. Using defaults.");
+            Client::new()
+        }
+    }
+}
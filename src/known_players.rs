@@ -0,0 +1,120 @@
+// Jogadores já vistos pelo overlay. São salvos pelo id da conta, então trocar de nome não perde nada.
+
+use std::{
+    fs,
+    path::Path,
+    sync::{Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+use serde_json::{json, Value};
+
+use crate::{paths, player::Player, util};
+
+pub fn get_known_players_file_path() -> String {
+    format!("{}/kc_overlay_players.json", paths::data_dir())
+}
+
+// Os jogadores ficam na memória e o arquivo é reescrito em lotes, não a cada jogador.
+struct KnownPlayers {
+    players: Value,
+    changed: bool,
+    saved_at: Instant,
+}
+
+fn known_players() -> &'static Mutex<KnownPlayers> {
+    static KNOWN_PLAYERS: OnceLock<Mutex<KnownPlayers>> = OnceLock::new();
+
+    KNOWN_PLAYERS.get_or_init(|| {
+        Mutex::new(KnownPlayers {
+            players: read_known_players(),
+            changed: false,
+            saved_at: Instant::now(),
+        })
+    })
+}
+
+fn read_known_players() -> Value {
+    let path = get_known_players_file_path();
+    if !Path::new(&path).exists() {
+        return json!({});
+    }
+    let error = match util::get_json(&path) {
+        Ok(players) if players.is_object() => return players,
+        Ok(_) => "expected a JSON object".to_string(),
+        Err(e) => e,
+    };
+    println!("Failed to read known players: {error}");
+
+    // O próximo save substitui o arquivo, então guarda o inválido para não perder o histórico.
+    if let Err(e) = fs::copy(&path, format!("{path}.invalid")) {
+        println!("Failed to keep invalid known players file: {e}");
+    }
+    json!({})
+}
+
+// Registra o jogador e preenche os nomes que ele já usou, caso tenha trocado de nome, e a última
+// vez que ele foi visto. Só muda a memória, ver `save`.
+pub fn remember(player: &mut Player) {
+    let Some(uuid) = player.uuid.clone() else {
+        return;
+    };
+
+    let mut known_players = known_players().lock().unwrap();
+    known_players.changed = true;
+    let now = chrono::Utc::now().timestamp_millis();
+
+    let entry = &mut known_players.players[uuid.as_str()];
+    if !entry.is_object() {
+        *entry = json!({
+            "username": player.username,
+            "former_usernames": [],
+            "first_seen": now,
+        });
+    }
+
+    let last_username = entry["username"].as_str().unwrap_or("").to_string();
+    if !last_username.is_empty() && !last_username.eq_ignore_ascii_case(&player.username) {
+        if let Some(former_usernames) = entry["former_usernames"].as_array_mut() {
+            former_usernames.retain(|x| x.as_str() != Some(player.username.as_str()));
+            former_usernames.push(json!(last_username));
+        }
+        entry["username"] = json!(player.username);
+    }
+    player.last_seen = entry["last_seen"].as_i64();
+    entry["last_seen"] = json!(now);
+
+    player.former_usernames = entry["former_usernames"]
+        .as_array()
+        .map(|x| {
+            x.iter()
+                .filter_map(|username| username.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+}
+
+// Salva se a última escrita foi há algum tempo. Usado a cada jogador consultado.
+pub fn save_if_due() {
+    let due = known_players().lock().unwrap().saved_at.elapsed() >= Duration::from_secs(30);
+    if due {
+        save();
+    }
+}
+
+// Salva as mudanças pendentes. Usado ao terminar a lista da sala e ao fechar o overlay.
+pub fn save() {
+    let mut known_players = known_players().lock().unwrap();
+    if !known_players.changed {
+        return;
+    }
+
+    known_players.saved_at = Instant::now();
+    match util::write_atomic(
+        &get_known_players_file_path(),
+        &serde_json::to_string_pretty(&known_players.players).unwrap(),
+    ) {
+        Ok(_) => known_players.changed = false,
+        Err(e) => println!("Failed to save known players: {e}"),
+    }
+}
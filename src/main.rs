@@ -1,18 +1,21 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 use std::{
+    collections::HashMap,
     env,
     fmt::Display,
-    fs::{self, File, OpenOptions},
-    io::{BufRead, BufReader, Seek, SeekFrom, Write},
+    fs::{self, File},
+    io::{BufRead, BufReader, Seek, SeekFrom},
     path::Path,
     time::Duration,
 };
 
+use cheater::{CheaterRule, RuleParameter};
+use color_tiers::ColorTiers;
+use config::Config;
 use iced::{
     event,
     futures::{
         channel::mpsc::{self, Sender},
-        executor::block_on,
         SinkExt, Stream, StreamExt,
     },
     mouse::Button,
@@ -22,16 +25,29 @@ use iced::{
     window::{self, Position, Settings},
     Color, Element, Font, Point, Size, Subscription, Task,
 };
-use player::Player;
+use player::{LookupKind, LookupState, Player, PlayerLookup, PlayerStatus, RetryPolicy};
 use screens::Screen;
-use stats::{Stats, StatsType};
+use session::Session;
+use snapshots::Snapshot;
+use stats::StatsType;
+use threat::ThreatWeight;
 use tokio::time::sleep;
 
+mod cheater;
+mod color_tiers;
 mod config;
+mod encounters;
+mod http;
+mod known_players;
+mod minecraft_text;
+mod paths;
 mod player;
 mod screens;
+mod session;
+mod snapshots;
 mod stats;
 mod themed_widgets;
+mod threat;
 mod update;
 mod util;
 
@@ -45,6 +61,8 @@ fn main() {
         }
     }
 
+    paths::setup();
+
     let icon = include_bytes!("../assets/icon.png");
 
     // Executa a lógica do programa.
@@ -85,14 +103,34 @@ struct KCOverlay {
     logs_sender: Option<mpsc::Sender<MineClient>>,
     player_getter_sender: Option<mpsc::Sender<()>>,
     update: Update,
-    never_minimize: bool,
-    seconds_to_minimize: u64,
-    auto_manage_players: bool,
+    // Configurações lidas ao abrir o overlay. Cada mudança altera este valor e salva o arquivo.
+    config: Config,
     player_to_view_username: String,
-    searched_player: Option<Player>,
+    searched_player: SearchedPlayer,
+    // Número da última busca, para ignorar respostas de buscas anteriores que chegarem depois.
+    search_id: u64,
     searched_player_stats_type: StatsType,
     stats_type: StatsType,
-    window_scale: f64,
+    retry_policy: RetryPolicy,
+    http_client: reqwest::Client,
+    // Texto digitado nos limites de cor, para não reformatar o número durante a edição.
+    color_tier_inputs: HashMap<(String, usize), String>,
+    // Nome digitado para o próprio jogador, usado para acompanhar a sessão.
+    own_username_input: String,
+    session: Session,
+    // Histórico de stats do jogador pesquisado, por id do modo.
+    searched_player_history: HashMap<String, Vec<Snapshot>>,
+    // Mostra a resposta da API na tela "Ver jogador".
+    show_raw_json: bool,
+    pending_lookups: Vec<(String, LookupState)>,
+    lobby: u64,
+    // Quando a sala atual começou (último /jogando), usado para registrar os encontros.
+    lobby_started_at: i64,
+    // Salas que terminaram desde a última consulta da sessão, (início, fim). Uma delas pode ser a
+    // da partida cujo resultado aparecer na próxima consulta.
+    ended_lobbies: Vec<(i64, i64)>,
+    // Aviso quando o arquivo de configuração estava inválido ao abrir o overlay.
+    config_warning: Option<String>,
 }
 
 // Mensagens enviadas para o programa saber quando atualizar variáveis, executar funções, e etc.
@@ -106,7 +144,7 @@ enum Message {
     ClientSelect(MineClient),
     ClientUpdate,
     Minimize,
-    PlayerSender(PlayerSender),
+    PlayerSender(u64, PlayerSender),
     CheckedUpdates(Result<String, String>),
     OpenLink(String),
     Update,
@@ -119,9 +157,31 @@ enum Message {
     ViewPlayerInputChanged(String),
     ViewPlayerStatsChanged(StatsType),
     ViewPlayer,
+    ViewPlayerResult(u64, PlayerLookup),
+    JoinedPlayer(u64, String, PlayerLookup),
+    // Para a busca, vem junto o histórico de stats do jogador.
+    PlayerRecorded(RecordedFor, Box<Player>, HashMap<String, Vec<Snapshot>>),
     StatsSelect(StatsType),
     WindowScaleChanged(f64),
     UpdateWaitTime,
+    ThreatWeightChanged(ThreatWeight, f64),
+    SortByThreatChanged(bool),
+    ThreatAlertThresholdChanged(f64),
+    ColorTierChanged(String, usize, String),
+    ResetColorTiers,
+    ExtraColumnToggled(String, bool),
+    SnapshotRetentionDaysChanged(f64),
+    SnapshotRetentionMaxChanged(f64),
+    ShowDeltasChanged(bool),
+    OwnUsernameInputChanged(String),
+    SetOwnUsername,
+    RefreshSession,
+    SessionStats(String, PlayerLookup),
+    ResetSession,
+    CheaterRuleToggled(CheaterRule, bool),
+    CheaterRuleParameterChanged(RuleParameter, f64),
+    ToggleRawJson,
+    DismissConfigWarning,
 }
 
 // Lógica principal do programa.
@@ -132,28 +192,27 @@ impl KCOverlay {
 
     // Função executada após o início da lógica. Ela coleta os dados do arquivo de configuração.
     fn new() -> (Self, Task<Message>) {
-        let is_first_use = config::check_config_file();
-
-        let config = config::get_config();
-        let custom_client_path = config["custom_client_path"]
-            .as_str()
-            .unwrap_or("")
-            .to_string();
-        let client = match config["client"].as_i64().unwrap_or(0) {
-            0 => MineClient::Default,
-            1 => MineClient::Badlion,
-            2 => MineClient::Lunar,
-            3 => MineClient::LegacyLauncher,
-            4 => MineClient::Custom(custom_client_path),
-            5 => MineClient::Silent,
-            _ => MineClient::Default,
+        let is_first_use = !config::config_file_exists();
+
+        // Salva logo após ler, assim configurações novas aparecem no arquivo. Se o arquivo estava
+        // inválido, não cria backup e só salva quando o usuário mudar alguma configuração.
+        let (mut config, config_warning) = Config::load();
+        // Modos salvos pelo nome em versões antigas passam a ser salvos pelo id.
+        let stats_type = StatsType::from_string(&config.stats_type);
+        config.stats_type = stats_type.id().to_string();
+        if config_warning.is_none() {
+            config::back_up();
+            config.save();
+        }
+
+        let client = MineClient::from_id(&config.client, config.custom_client_path.clone());
+        let window_scale = config.window_scale;
+        let retry_policy = RetryPolicy {
+            max_retries: config.max_retries,
+            timeout: Duration::from_secs(config.request_timeout),
         };
-        let never_minimize = config["never_minimize"].as_bool().unwrap_or(false);
-        let seconds_to_minimize = config["seconds_to_minimize"].as_u64().unwrap_or(10);
-        let auto_manage_players = config["auto_manage_players"].as_bool().unwrap_or(true);
-        let stats_type_str = config["stats_type"].as_str().unwrap_or("Bedwars Geral");
-        let stats_type = StatsType::from_string(stats_type_str);
-        let window_scale = config["window_scale"].as_f64().unwrap_or(1.0);
+        let http_client = http::build_client(&config);
+        let snapshot_retention = config.snapshot_retention;
 
         let screen = if is_first_use {
             Screen::Welcome
@@ -161,6 +220,27 @@ impl KCOverlay {
             Screen::Main
         };
 
+        let mut tasks = vec![
+            Task::perform(
+                update::check_updates(http_client.clone()),
+                Message::CheckedUpdates,
+            ),
+            window::get_latest().and_then(move |x| {
+                window::resize(
+                    x,
+                    Size::new(745. * window_scale as f32, 460. * window_scale as f32),
+                )
+            }),
+            Task::future(util::blocking(move || snapshots::prune(snapshot_retention))).discard(),
+        ];
+        if !config.username.is_empty() {
+            tasks.push(fetch_session_stats(
+                http_client.clone(),
+                config.username.clone(),
+                retry_policy,
+            ));
+        }
+
         (
             Self {
                 screen,
@@ -171,24 +251,26 @@ impl KCOverlay {
                 logs_sender: None,
                 player_getter_sender: None,
                 update: Update::empty(),
-                never_minimize,
-                seconds_to_minimize,
-                auto_manage_players,
                 player_to_view_username: String::new(),
-                searched_player: None,
-                searched_player_stats_type: StatsType::BedwarsAll,
+                searched_player: SearchedPlayer::None,
+                search_id: 0,
+                searched_player_stats_type: StatsType::default(),
                 stats_type,
-                window_scale,
+                retry_policy,
+                http_client,
+                color_tier_inputs: HashMap::new(),
+                own_username_input: config.username.clone(),
+                session: Session::new(),
+                searched_player_history: HashMap::new(),
+                show_raw_json: false,
+                pending_lookups: vec![],
+                lobby: 0,
+                lobby_started_at: 0,
+                ended_lobbies: vec![],
+                config,
+                config_warning,
             },
-            Task::batch(vec![
-                Task::perform(update::check_updates(), Message::CheckedUpdates),
-                window::get_latest().and_then(move |x| {
-                    window::resize(
-                        x,
-                        Size::new(745. * window_scale as f32, 460. * window_scale as f32),
-                    )
-                }),
-            ]),
+            Task::batch(tasks),
         )
     }
 
@@ -206,22 +288,60 @@ impl KCOverlay {
 
             Message::Log(log_reader) => match log_reader {
                 LogReader::Log(message) => {
+                    let mut join_tasks = vec![];
+
+                    // O client mostra o nome da conta ao iniciar o jogo.
+                    if let Some((_, username)) = message.split_once("Setting user: ") {
+                        let username = username.trim().to_string();
+                        if !username.is_empty() && username != self.config.username {
+                            self.own_username_input = username.clone();
+                            join_tasks.push(self.set_own_username(username));
+                        }
+                    }
+
                     // Checa se algum jogador entrou na partida.
-                    if self.auto_manage_players {
+                    if self.config.auto_manage_players {
                         if message.contains("entrou na sala") && !self.players.is_empty() {
                             // com certeza não é a maneira mais eficiente de fazer isso!
                             let splitted_message: Vec<&str> = message.split(" ").collect();
                             for (index, part) in splitted_message.clone().into_iter().enumerate() {
-                                if part == "entrou" {
-                                    let player_name = splitted_message[index - 1];
-                                    let player = block_on(async {
-                                        player::get_player(player_name, self.stats_type.clone())
-                                            .await
-                                    });
-
-                                    if let Ok(ok) = player {
-                                        self.add_player(ok);
+                                if part == "entrou" && index > 0 {
+                                    let player_name = splitted_message[index - 1].to_string();
+                                    let already_listed = self
+                                        .players
+                                        .iter()
+                                        .any(|player| player.username == player_name)
+                                        || self
+                                            .pending_lookups
+                                            .iter()
+                                            .any(|(username, _)| *username == player_name);
+                                    if already_listed {
+                                        continue;
                                     }
+
+                                    // Cada jogador que entrou é consultado em paralelo, sem travar a interface.
+                                    self.set_lookup_state(
+                                        player_name.clone(),
+                                        LookupState::Loading,
+                                    );
+                                    let http_client = self.http_client.clone();
+                                    let retry_policy = self.retry_policy;
+                                    let lobby = self.lobby;
+                                    join_tasks.push(Task::run(
+                                        player::get_player(
+                                            http_client,
+                                            player_name.clone(),
+                                            retry_policy,
+                                            LookupKind::Lobby,
+                                        ),
+                                        move |lookup| {
+                                            Message::JoinedPlayer(
+                                                lobby,
+                                                player_name.clone(),
+                                                lookup,
+                                            )
+                                        },
+                                    ));
                                 }
                             }
                         }
@@ -243,6 +363,8 @@ impl KCOverlay {
                         }
                     }
 
+                    join_tasks.push(self.record_opponents(&message));
+
                     // Checa se a mensagem possui a lista de jogadores de quando o jogador digita "/jogando".
                     if message.contains("[CHAT] Jogadores") && self.waiting < 1 {
                         let split = message.split("):").map(|x| x.to_string());
@@ -257,12 +379,27 @@ impl KCOverlay {
                             .collect();
 
                         self.players.clear();
+                        self.pending_lookups.clear();
                         self.loading = true;
+                        self.lobby += 1;
+                        let now = chrono::Utc::now().timestamp_millis();
+                        if self.lobby_started_at > 0 {
+                            self.ended_lobbies.push((self.lobby_started_at, now));
+                        }
+                        self.lobby_started_at = now;
 
+                        let lobby = self.lobby;
                         Task::batch(vec![
                             Task::run(
-                                player::get_players(str_players, self.stats_type.clone()),
-                                |player_sender: PlayerSender| Message::PlayerSender(player_sender),
+                                player::get_players(
+                                    self.http_client.clone(),
+                                    str_players,
+                                    self.retry_policy,
+                                    self.config.max_concurrent_requests,
+                                ),
+                                move |player_sender: PlayerSender| {
+                                    Message::PlayerSender(lobby, player_sender)
+                                },
                             ),
                             window::get_latest().and_then(|x| {
                                 window::set_level(x, iced::window::Level::AlwaysOnTop)
@@ -270,7 +407,7 @@ impl KCOverlay {
                             window::get_latest().and_then(|x| window::minimize(x, false)),
                         ])
                     } else {
-                        Task::none()
+                        Task::batch(join_tasks)
                     }
                 }
                 LogReader::Sender(mut sender) => {
@@ -281,7 +418,7 @@ impl KCOverlay {
             },
             // Minimiza a janela
             Message::ChangeLevel => {
-                if self.loading || self.never_minimize {
+                if self.loading || self.config.never_minimize {
                     Task::none()
                 } else {
                     Task::batch(vec![
@@ -296,40 +433,23 @@ impl KCOverlay {
                 }
                 _ => Task::none(),
             },
-            Message::Close => window::get_latest().and_then(window::close),
+            Message::Close => {
+                known_players::save();
+                window::get_latest().and_then(window::close)
+            }
             // Seleciona o Client e salva no arquivo de configuração.
             Message::ClientSelect(mine_client) => {
                 self.client = mine_client.clone();
 
-                let mut config = config::get_config();
-                let client_number = match mine_client {
-                    MineClient::Default => 0,
-                    MineClient::Badlion => 1,
-                    MineClient::Lunar => 2,
-                    MineClient::LegacyLauncher => 3,
-                    MineClient::Custom(path) => {
-                        if !path.eq(" ") {
-                            config["custom_client_path"] = serde_json::json!(path);
-                        } else {
-                            let custom_client_path =
-                                config["custom_client_path"].as_str().unwrap().to_string();
-                            self.client = MineClient::Custom(custom_client_path)
-                        }
-                        4
+                if let MineClient::Custom(path) = mine_client {
+                    if !path.eq(" ") {
+                        self.config.custom_client_path = path;
+                    } else {
+                        self.client = MineClient::Custom(self.config.custom_client_path.clone())
                     }
-                    MineClient::Silent => 5,
-                };
-
-                config["client"] = serde_json::json!(client_number);
-
-                let mut config_file = OpenOptions::new()
-                    .write(true)
-                    .truncate(true)
-                    .open(config::get_config_file_path())
-                    .unwrap();
-                config_file
-                    .write_all(serde_json::to_string_pretty(&config).unwrap().as_bytes())
-                    .unwrap();
+                }
+                self.config.client = self.client.id().to_string();
+                self.config.save();
 
                 if let Screen::Welcome = self.screen {
                     self.screen = Screen::Main
@@ -351,25 +471,45 @@ impl KCOverlay {
                 None => Task::none(),
             },
             // Gerencia o output do código responsável por ler os logs.
-            Message::PlayerSender(player_sender) => match player_sender {
+            Message::PlayerSender(lobby, player_sender) if lobby != self.lobby => {
+                // Resto da consulta de uma sala anterior. Só interrompe a consulta e respeita o
+                // limite da API.
+                match player_sender {
+                    PlayerSender::Sender(mut sender) => {
+                        let _ = sender.try_send(());
+                    }
+                    PlayerSender::WaitOrder => self.wait_rate_limit(),
+                    _ => {}
+                }
+                Task::none()
+            }
+            Message::PlayerSender(lobby, player_sender) => match player_sender {
                 PlayerSender::Player(player) => {
-                    self.add_player(player);
+                    self.record_player(player, RecordedFor::Lobby(lobby))
+                }
+                PlayerSender::Retrying(username, attempt) => {
+                    self.set_lookup_state(username, LookupState::Retrying(attempt));
+                    Task::none()
+                }
+                PlayerSender::Failed(username) => {
+                    self.set_lookup_state(username, LookupState::Failed);
                     Task::none()
                 }
                 PlayerSender::Done => {
                     self.loading = false;
                     self.player_getter_sender = None;
-                    Task::perform(
-                        util::wait(Duration::from_secs(self.seconds_to_minimize)),
-                        |_| Message::ChangeLevel,
-                    )
+                    Task::batch(vec![
+                        Task::perform(
+                            util::wait(Duration::from_secs(self.config.seconds_to_minimize)),
+                            |_| Message::ChangeLevel,
+                        ),
+                        Task::future(util::blocking(known_players::save)).discard(),
+                    ])
                 }
                 PlayerSender::Sender(new_sender) => {
                     match self.player_getter_sender.clone() {
                         Some(mut sender) => {
-                            block_on(async {
-                                sender.send(()).await.unwrap();
-                            });
+                            let _ = sender.try_send(());
                             self.player_getter_sender = Some(new_sender)
                         }
                         None => self.player_getter_sender = Some(new_sender),
@@ -377,7 +517,7 @@ impl KCOverlay {
                     Task::none()
                 }
                 PlayerSender::WaitOrder => {
-                    self.waiting = 50;
+                    self.wait_rate_limit();
                     Task::none()
                 }
             },
@@ -402,7 +542,7 @@ impl KCOverlay {
             Message::Update => {
                 self.update.available = false;
                 Task::perform(
-                    update::install_update(self.update.url.clone()),
+                    update::install_update(self.http_client.clone(), self.update.url.clone()),
                     Message::UpdateResult,
                 )
             }
@@ -428,8 +568,14 @@ impl KCOverlay {
 
                         new_exe_path = new_exe_path.join(exec_name);
 
-                        match std::process::Command::new(new_exe_path).spawn() {
-                            Ok(_) => std::process::exit(0),
+                        match std::process::Command::new(new_exe_path)
+                            .args(env::args().skip(1))
+                            .spawn()
+                        {
+                            Ok(_) => {
+                                known_players::save();
+                                std::process::exit(0)
+                            }
                             Err(e) => panic!("{}", e),
                         }
                     }
@@ -454,19 +600,19 @@ impl KCOverlay {
                 )
             }
             Message::ChangeNeverMinimize(bool) => {
-                self.never_minimize = bool;
-                config::save_settings(Some(bool), None, None, None, None);
+                self.config.never_minimize = bool;
+                self.config.save();
                 Task::none()
             }
             Message::ChangeSecondsToMinimize(f_seconds) => {
                 let u_seconds = f_seconds as u64;
-                self.seconds_to_minimize = u_seconds;
-                config::save_settings(None, Some(u_seconds), None, None, None);
+                self.config.seconds_to_minimize = u_seconds;
+                self.config.save();
                 Task::none()
             }
             Message::ChangeRemoveEliminatedPlayers(bool) => {
-                self.auto_manage_players = bool;
-                config::save_settings(None, None, Some(bool), None, None);
+                self.config.auto_manage_players = bool;
+                self.config.save();
                 Task::none()
             }
             Message::ViewPlayerInputChanged(text) => {
@@ -479,31 +625,91 @@ impl KCOverlay {
             }
 
             Message::ViewPlayer => {
-                match block_on(async {
-                    player::get_player(
-                        &self.player_to_view_username,
-                        self.searched_player_stats_type.clone(),
-                    )
-                    .await
-                }) {
-                    Ok(player) => {
-                        self.searched_player = Some(player);
+                self.searched_player = SearchedPlayer::Loading;
+                self.search_id += 1;
+
+                let username = self.player_to_view_username.clone();
+                let http_client = self.http_client.clone();
+                let retry_policy = self.retry_policy;
+                let search_id = self.search_id;
+                Task::run(
+                    player::get_player(http_client, username, retry_policy, LookupKind::Search),
+                    move |lookup| Message::ViewPlayerResult(search_id, lookup),
+                )
+            }
+            // O limite de consultas vale para todas, mesmo se a busca já foi substituída.
+            Message::ViewPlayerResult(_, PlayerLookup::RateLimited)
+            | Message::JoinedPlayer(_, _, PlayerLookup::RateLimited)
+            | Message::SessionStats(_, PlayerLookup::RateLimited) => {
+                self.wait_rate_limit();
+                Task::none()
+            }
+            // Uma busca mais nova já foi feita.
+            Message::ViewPlayerResult(search_id, _) if search_id != self.search_id => Task::none(),
+            Message::ViewPlayerResult(_, PlayerLookup::Retrying(attempt)) => {
+                self.searched_player = SearchedPlayer::Retrying(attempt);
+                Task::none()
+            }
+            Message::ViewPlayerResult(_, PlayerLookup::Done(result)) => match result {
+                Ok(player) => self.record_player(player, RecordedFor::Search(self.search_id)),
+                Err(_) => {
+                    self.searched_player = SearchedPlayer::Failed;
+                    Task::none()
+                }
+            },
+            Message::JoinedPlayer(lobby, username, lookup) => {
+                // A sala mudou enquanto o jogador era consultado.
+                if lobby != self.lobby {
+                    return Task::none();
+                }
+                match lookup {
+                    PlayerLookup::Retrying(attempt) => {
+                        self.set_lookup_state(username, LookupState::Retrying(attempt));
+                        Task::none()
+                    }
+                    PlayerLookup::Done(Ok(player)) => {
+                        self.record_player(player, RecordedFor::Lobby(lobby))
+                    }
+                    PlayerLookup::Done(Err(_)) => {
+                        self.set_lookup_state(username, LookupState::Failed);
+                        Task::none()
                     }
-                    Err(_) => (),
+                    PlayerLookup::RateLimited => Task::none(),
                 }
-
+            }
+            Message::PlayerRecorded(RecordedFor::Lobby(lobby), mut player, _) => {
+                // A sala mudou enquanto o jogador era registrado.
+                if lobby != self.lobby {
+                    return Task::none();
+                }
+                self.pending_lookups
+                    .retain(|(username, _)| *username != player.username);
+                player.cheater_flags = self.config.cheater_rules.evaluate(&player);
+                let task = self.record_encounter(&player);
+                self.add_player(*player);
+                task
+            }
+            Message::PlayerRecorded(RecordedFor::Search(search_id), mut player, history) => {
+                if search_id != self.search_id {
+                    return Task::none();
+                }
+                player.cheater_flags = self.config.cheater_rules.evaluate(&player);
+                self.searched_player_history = history;
+                self.searched_player = SearchedPlayer::Found(*player);
                 Task::none()
             }
             Message::StatsSelect(stats_type) => {
+                // Os stats de todos os modos já estão salvos, então só reordena a lista.
                 self.stats_type = stats_type.clone();
-                self.players.clear();
-                config::save_settings(None, None, None, Some(stats_type.to_string()), None);
+                self.config.stats_type = stats_type.id().to_string();
+                self.sort_players();
+                self.config.save();
                 Task::none()
             }
             Message::WindowScaleChanged(scale) => {
                 let scale = scale / 100.;
-                self.window_scale = scale;
-                config::save_settings(None, None, None, None, Some(scale));
+                self.config.window_scale = scale;
+                self.config.save();
                 window::get_latest().and_then(move |x| {
                     window::resize(x, Size::new(745. * scale as f32, 460. * scale as f32))
                 })
@@ -512,6 +718,152 @@ impl KCOverlay {
                 self.waiting -= 1;
                 Task::none()
             }
+            Message::ThreatWeightChanged(weight, value) => {
+                self.config.threat.set_weight(weight, value);
+                self.save_threat_settings();
+                Task::none()
+            }
+            Message::SortByThreatChanged(sort_by_threat) => {
+                self.config.threat.sort_by_threat = sort_by_threat;
+                self.save_threat_settings();
+                Task::none()
+            }
+            Message::ThreatAlertThresholdChanged(threshold) => {
+                self.config.threat.alert_threshold = threshold;
+                self.save_threat_settings();
+                Task::none()
+            }
+            Message::ColorTierChanged(stat, index, input) => {
+                // Valores inválidos ou fora de ordem ficam só no campo, marcado em vermelho.
+                let value = input.replace(',', ".").parse::<f64>();
+                if value
+                    .is_ok_and(|value| self.config.color_tiers.set_threshold(&stat, index, value))
+                {
+                    self.config.save();
+                }
+                self.color_tier_inputs.insert((stat, index), input);
+                Task::none()
+            }
+            Message::ResetColorTiers => {
+                self.config.color_tiers = ColorTiers::default();
+                self.color_tier_inputs.clear();
+                self.config.save();
+                Task::none()
+            }
+            Message::ExtraColumnToggled(stat, enabled) => {
+                self.config.extra_columns.retain(|column| *column != stat);
+                if enabled {
+                    self.config.extra_columns.push(stat);
+                }
+                self.config.save();
+                Task::none()
+            }
+            Message::SnapshotRetentionDaysChanged(days) => {
+                self.config.snapshot_retention.days = days as u64;
+                self.config.save();
+                Task::none()
+            }
+            Message::SnapshotRetentionMaxChanged(max_per_mode) => {
+                self.config.snapshot_retention.max_per_mode = max_per_mode as u64;
+                self.config.save();
+                Task::none()
+            }
+            Message::ShowDeltasChanged(show_deltas) => {
+                self.config.show_deltas = show_deltas;
+                self.config.save();
+                Task::none()
+            }
+            Message::OwnUsernameInputChanged(username) => {
+                self.own_username_input = username;
+                Task::none()
+            }
+            Message::SetOwnUsername => {
+                let username = self.own_username_input.trim().to_string();
+                self.set_own_username(username)
+            }
+            Message::RefreshSession => fetch_session_stats(
+                self.http_client.clone(),
+                self.config.username.clone(),
+                self.retry_policy,
+            ),
+            Message::SessionStats(username, lookup) => {
+                // Ignora respostas de um nome que já foi trocado.
+                if username != self.config.username {
+                    return Task::none();
+                }
+                let PlayerLookup::Done(Ok(player)) = lookup else {
+                    return Task::none();
+                };
+                if player.status != PlayerStatus::Found {
+                    return Task::none();
+                }
+                let known_matches = self.session.matches.len();
+                self.session.update(*player);
+
+                let new_matches = &self.session.matches[known_matches..];
+                let gained = |stat: &str| {
+                    new_matches
+                        .iter()
+                        .any(|x| x.deltas.get(stat).is_some_and(|delta| *delta > 0.))
+                };
+                let won = if gained("wins") {
+                    Some(true)
+                } else if gained("losses") {
+                    Some(false)
+                } else {
+                    None
+                };
+
+                let Some(won) = won else {
+                    return Task::none();
+                };
+
+                // A partida terminou depois da última consulta com resultado. Se alguma sala
+                // terminou nesse tempo, é a que durou mais, já que as outras foram só abertas e
+                // deixadas. Senão, a partida é da sala atual.
+                let lobby_at = self
+                    .ended_lobbies
+                    .iter()
+                    .max_by_key(|(started_at, ended_at)| ended_at - started_at)
+                    .map(|(started_at, _)| *started_at)
+                    .unwrap_or(self.lobby_started_at);
+                // Só descarta as salas que terminaram depois que uma delas recebeu o resultado.
+                self.ended_lobbies.clear();
+
+                if lobby_at == 0 {
+                    return Task::none();
+                }
+                Task::future(util::blocking(move || {
+                    encounters::set_result(lobby_at, won)
+                }))
+                .discard()
+            }
+            Message::ResetSession => {
+                self.session = Session::new();
+                fetch_session_stats(
+                    self.http_client.clone(),
+                    self.config.username.clone(),
+                    self.retry_policy,
+                )
+            }
+            Message::ToggleRawJson => {
+                self.show_raw_json = !self.show_raw_json;
+                Task::none()
+            }
+            Message::DismissConfigWarning => {
+                self.config_warning = None;
+                Task::none()
+            }
+            Message::CheaterRuleToggled(rule, enabled) => {
+                self.config.cheater_rules.set_enabled(rule, enabled);
+                self.save_cheater_rules();
+                Task::none()
+            }
+            Message::CheaterRuleParameterChanged(parameter, value) => {
+                self.config.cheater_rules.set_parameter(parameter, value);
+                self.save_cheater_rules();
+                Task::none()
+            }
         }
     }
 
@@ -535,26 +887,154 @@ impl KCOverlay {
             subscriptions.push(time::every(Duration::from_secs(1)).map(|_| Message::UpdateWaitTime))
         }
 
+        // Atualiza os stats da sessão a cada 2 minutos.
+        if !self.config.username.is_empty() {
+            subscriptions
+                .push(time::every(Duration::from_secs(120)).map(|_| Message::RefreshSession))
+        }
+
         Subscription::batch(subscriptions)
     }
 
     fn scale_factor(&self) -> f64 {
-        self.window_scale
+        self.config.window_scale
     }
 
     fn add_player(&mut self, player: Player) {
         self.players.push(player);
-        self.players.sort_by(|a, b| {
-            let b_level = match &b.stats {
-                Stats::Bedwars(bedwars) => bedwars.level,
-            };
-            let a_level = match &a.stats {
-                Stats::Bedwars(bedwars) => bedwars.level,
-            };
-            b_level.partial_cmp(&a_level).unwrap()
-        });
+        self.sort_players();
         self.players.truncate(16);
     }
+
+    fn sort_players(&mut self) {
+        let stats_type = self.stats_type.clone();
+        let threat_settings = self.config.threat;
+        // Nicks ficam no topo, jogadores sem stats no final.
+        let sort_value = |player: &Player| match (&player.status, player.stats(&stats_type)) {
+            (PlayerStatus::Nicked, _) => f64::INFINITY,
+            (PlayerStatus::Found, Some(stats)) if threat_settings.sort_by_threat => {
+                threat_settings.score(stats)
+            }
+            (PlayerStatus::Found, Some(stats)) => stats.level() as f64,
+            _ => -1.,
+        };
+        self.players
+            .sort_by(|a, b| sort_value(b).total_cmp(&sort_value(a)));
+    }
+
+    fn save_threat_settings(&mut self) {
+        self.sort_players();
+        self.config.save();
+    }
+
+    // Guarda o jogador na lista de jogadores conhecidos e no histórico de stats.
+    // Preenche o que já sabemos do jogador e salva seus stats, fora da thread da interface.
+    fn record_player(&self, mut player: Box<Player>, recorded_for: RecordedFor) -> Task<Message> {
+        let snapshot_retention = self.config.snapshot_retention;
+        Task::perform(
+            util::blocking(move || {
+                known_players::remember(&mut player);
+                let mut history = HashMap::new();
+                if let Some(uuid) = &player.uuid {
+                    player.last_snapshots = snapshots::latest(uuid);
+                    player.encounters = encounters::list(uuid);
+                    if let RecordedFor::Search(_) = recorded_for {
+                        history = snapshots::history(uuid);
+                    }
+                }
+                snapshots::save(&player, snapshot_retention);
+                known_players::save_if_due();
+                (player, history)
+            }),
+            move |(player, history)| Message::PlayerRecorded(recorded_for, player, history),
+        )
+    }
+
+    // Reavalia os jogadores da sala com as regras atuais e salva as regras.
+    fn save_cheater_rules(&mut self) {
+        for player in self.players.iter_mut() {
+            player.cheater_flags = self.config.cheater_rules.evaluate(player);
+        }
+        self.config.save();
+    }
+
+    fn record_encounter(&self, player: &Player) -> Task<Message> {
+        let (Some(uuid), true) = (player.uuid.clone(), self.lobby_started_at > 0) else {
+            return Task::none();
+        };
+        let username = player.username.clone();
+        let lobby_at = self.lobby_started_at;
+        let mode = self.stats_type.id().to_string();
+        Task::future(util::blocking(move || {
+            encounters::record(&uuid, &username, lobby_at, &mode)
+        }))
+        .discard()
+    }
+
+    // Troca o jogador acompanhado e começa uma nova sessão.
+    fn set_own_username(&mut self, username: String) -> Task<Message> {
+        self.config.username = username.clone();
+        self.session = Session::new();
+        self.config.save();
+        if username.is_empty() {
+            Task::none()
+        } else {
+            fetch_session_stats(self.http_client.clone(), username, self.retry_policy)
+        }
+    }
+
+    // Quem matou o usuário ou foi morto por ele nesta sala estava no time adversário. As mensagens
+    // de morte começam com o nome de quem morreu, seguido de "morreu".
+    fn record_opponents(&self, message: &str) -> Task<Message> {
+        let own_username = self.config.username.as_str();
+        let Some((victim, killer)) = message.split_once(" morreu") else {
+            return Task::none();
+        };
+        if own_username.is_empty() || self.lobby_started_at == 0 {
+            return Task::none();
+        }
+
+        let victim = victim.split_whitespace().last().unwrap_or("");
+        let killer_names: Vec<&str> = killer
+            .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .collect();
+        let uuids: Vec<String> = self
+            .players
+            .iter()
+            .filter(|player| {
+                (victim == own_username && killer_names.contains(&player.username.as_str()))
+                    || (victim == player.username && killer_names.contains(&own_username))
+            })
+            .filter_map(|player| player.uuid.clone())
+            .collect();
+        if uuids.is_empty() {
+            return Task::none();
+        }
+
+        let lobby_at = self.lobby_started_at;
+        Task::future(util::blocking(move || {
+            for uuid in uuids {
+                encounters::set_opponent(&uuid, lobby_at);
+            }
+        }))
+        .discard()
+    }
+
+    // Bloqueia novas consultas da sala até o limite da API ser renovado.
+    fn wait_rate_limit(&mut self) {
+        self.waiting = 50;
+    }
+
+    fn set_lookup_state(&mut self, username: String, state: LookupState) {
+        match self
+            .pending_lookups
+            .iter_mut()
+            .find(|(pending, _)| *pending == username)
+        {
+            Some((_, pending_state)) => *pending_state = state,
+            None => self.pending_lookups.push((username, state)),
+        }
+    }
 }
 
 // Output do leitor de logs.
@@ -673,12 +1153,30 @@ fn get_logs_path(client: MineClient) -> String {
 // Output do código responsável por obter os stats dos players.
 #[derive(Clone, Debug)]
 enum PlayerSender {
-    Player(Player),
+    Player(Box<Player>),
+    Retrying(String, u32),
+    Failed(String),
     WaitOrder,
     Sender(mpsc::Sender<()>),
     Done,
 }
 
+fn fetch_session_stats(
+    http_client: reqwest::Client,
+    username: String,
+    retry_policy: RetryPolicy,
+) -> Task<Message> {
+    Task::run(
+        player::get_player(
+            http_client,
+            username.clone(),
+            retry_policy,
+            LookupKind::Session,
+        ),
+        move |lookup| Message::SessionStats(username.clone(), lookup),
+    )
+}
+
 async fn update_client(mut sender: Sender<MineClient>, client: MineClient) {
     sender.send(client).await.unwrap();
 }
@@ -709,6 +1207,50 @@ impl Display for MineClient {
     }
 }
 
+impl MineClient {
+    // Id salvo no arquivo de configuração. Não muda com a ordem do enum nem com a tradução.
+    fn id(&self) -> &'static str {
+        match self {
+            MineClient::Default => "default",
+            MineClient::Badlion => "badlion",
+            MineClient::Lunar => "lunar",
+            MineClient::LegacyLauncher => "legacy_launcher",
+            MineClient::Custom(_) => "custom",
+            MineClient::Silent => "silent",
+        }
+    }
+
+    fn from_id(id: &str, custom_client_path: String) -> Self {
+        match id {
+            "badlion" => MineClient::Badlion,
+            "lunar" => MineClient::Lunar,
+            "legacy_launcher" => MineClient::LegacyLauncher,
+            "custom" => MineClient::Custom(custom_client_path),
+            "silent" => MineClient::Silent,
+            _ => MineClient::Default,
+        }
+    }
+}
+
+// Onde mostrar um jogador depois de registrado: na sala com esse número ou na busca com esse
+// número.
+#[derive(Debug, Clone, Copy)]
+enum RecordedFor {
+    Lobby(u64),
+    Search(u64),
+}
+
+// Estado da busca na tela "Ver jogador".
+#[derive(Default)]
+enum SearchedPlayer {
+    #[default]
+    None,
+    Loading,
+    Retrying(u32),
+    Found(Player),
+    Failed,
+}
+
 // Estrutura de um update.
 #[derive(Default)]
 struct Update {
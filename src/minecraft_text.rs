@@ -0,0 +1,143 @@
+// Texto no formato do Minecraft: códigos legados com § ou &, estilos e cores hex.
+
+use crate::util::Rgb;
+
+// Cor em hex ("#55ff55") ou código legado ("§a", "&a").
+pub fn parse_color(color: &str) -> Option<Rgb> {
+    let color = color.trim();
+    match color.strip_prefix('§').or(color.strip_prefix('&')) {
+        Some(code) => {
+            let code = code.chars().next()?.to_ascii_lowercase();
+            code.is_ascii_hexdigit()
+                .then(|| Rgb::from_minecraft_color(&code))
+        }
+        None => Rgb::from_hex(color),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextStyle {
+    pub color: Rgb,
+    pub bold: bool,
+    pub italic: bool,
+    pub underlined: bool,
+    pub strikethrough: bool,
+    pub obfuscated: bool,
+}
+
+impl TextStyle {
+    pub fn new(color: Rgb) -> Self {
+        TextStyle {
+            color,
+            bold: false,
+            italic: false,
+            underlined: false,
+            strikethrough: false,
+            obfuscated: false,
+        }
+    }
+}
+
+// Parte do texto com um só estilo.
+#[derive(Debug, Clone)]
+pub struct TextSpan {
+    pub text: String,
+    pub style: TextStyle,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MinecraftText {
+    pub spans: Vec<TextSpan>,
+}
+
+impl MinecraftText {
+    // Texto sem códigos, todo em uma cor.
+    pub fn plain(text: &str, color: Rgb) -> Self {
+        let mut minecraft_text = MinecraftText::default();
+        minecraft_text.push(text, &TextStyle::new(color));
+        minecraft_text
+    }
+
+    // Aceita "§a", "&a", "§l" (e os outros estilos), "§r", "§#rrggbb", "&#rrggbb" e o formato
+    // "§x§r§r§g§g§b§b" dos servidores. `default_color` é a cor antes do primeiro código e após §r.
+    pub fn parse(input: &str, default_color: Rgb) -> Self {
+        let mut minecraft_text = MinecraftText::default();
+        let mut style = TextStyle::new(default_color.clone());
+        let chars: Vec<char> = input.chars().collect();
+        let mut index = 0;
+
+        while index < chars.len() {
+            let c = chars[index];
+            let code = chars.get(index + 1).map(|x| x.to_ascii_lowercase());
+            let (Some(code), true) = (code, c == '§' || c == '&') else {
+                minecraft_text.push(&c.to_string(), &style);
+                index += 1;
+                continue;
+            };
+
+            // Cores hex, ex: §#ff5555 ou §x§f§f§5§5§5§5.
+            if code == '#' {
+                let hex: String = chars.iter().skip(index + 2).take(6).collect();
+                if let Some(color) = Rgb::from_hex(&hex).filter(|_| hex.len() == 6) {
+                    style = TextStyle::new(color);
+                    index += 8;
+                    continue;
+                }
+            }
+            if code == 'x' {
+                let hex: Option<String> = (0..6)
+                    .map(|x| {
+                        let position = index + 2 + x * 2;
+                        match (chars.get(position), chars.get(position + 1)) {
+                            (Some('§') | Some('&'), Some(digit)) => Some(*digit),
+                            _ => None,
+                        }
+                    })
+                    .collect();
+                if let Some(color) = hex.as_deref().and_then(Rgb::from_hex) {
+                    style = TextStyle::new(color);
+                    index += 14;
+                    continue;
+                }
+            }
+
+            match code {
+                '0'..='9' | 'a'..='f' => style = TextStyle::new(Rgb::from_minecraft_color(&code)),
+                'k' => style.obfuscated = true,
+                'l' => style.bold = true,
+                'm' => style.strikethrough = true,
+                'n' => style.underlined = true,
+                'o' => style.italic = true,
+                'r' => style = TextStyle::new(default_color.clone()),
+                // Não é um código, então o caractere aparece normalmente.
+                _ => {
+                    minecraft_text.push(&c.to_string(), &style);
+                    index += 1;
+                    continue;
+                }
+            }
+            index += 2;
+        }
+
+        minecraft_text
+    }
+
+    pub fn push(&mut self, text: &str, style: &TextStyle) {
+        if text.is_empty() {
+            return;
+        }
+        match self.spans.last_mut() {
+            Some(span) if span.style == *style => span.text.push_str(text),
+            _ => self.spans.push(TextSpan {
+                text: text.to_string(),
+                style: style.clone(),
+            }),
+        }
+    }
+
+    pub fn append(&mut self, other: MinecraftText) {
+        for span in other.spans {
+            self.push(&span.text, &span.style);
+        }
+    }
+}
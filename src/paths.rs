@@ -0,0 +1,156 @@
+// Pastas onde o overlay guarda configurações e dados. Segue o padrão do sistema (XDG no Linux,
+// %APPDATA% no Windows, Application Support no macOS). No modo portátil, ativado com `--portable`
+// ou com um arquivo `portable` junto do executável, tudo fica na pasta do executável.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::util;
+
+struct Paths {
+    config_dir: String,
+    data_dir: String,
+    // Caminho passado com `--config`, se houver.
+    config_file: Option<String>,
+}
+
+fn paths() -> &'static Paths {
+    static PATHS: OnceLock<Paths> = OnceLock::new();
+
+    PATHS.get_or_init(|| {
+        let args: Vec<String> = env::args().skip(1).collect();
+        let config_file = args
+            .iter()
+            .position(|x| x == "--config")
+            .and_then(|index| args.get(index + 1))
+            .map(String::as_str)
+            .or_else(|| args.iter().find_map(|x| x.strip_prefix("--config=")))
+            .map(|x| x.replace('\\', "/"));
+
+        let portable_dir = executable_dir()
+            .filter(|dir| args.iter().any(|x| x == "--portable") || dir.join("portable").exists());
+        let (config_dir, data_dir) = match portable_dir {
+            Some(dir) => {
+                let dir = dir.to_string_lossy().replace('\\', "/");
+                (dir.clone(), dir)
+            }
+            None => platform_dirs(),
+        };
+
+        Paths {
+            config_dir,
+            data_dir,
+            config_file,
+        }
+    })
+}
+
+fn executable_dir() -> Option<PathBuf> {
+    env::current_exe()
+        .ok()?
+        .parent()
+        .map(|dir| dir.to_path_buf())
+}
+
+fn platform_dirs() -> (String, String) {
+    match env::consts::OS {
+        "windows" => {
+            let app_data = env::var("APPDATA")
+                .map(|x| x.replace('\\', "/"))
+                .unwrap_or_else(|_| util::get_home_dir());
+            let dir = format!("{app_data}/KC-Overlay");
+            (dir.clone(), dir)
+        }
+        // `util::get_home_dir` não suporta macOS, então usa o HOME diretamente.
+        "macos" => {
+            let home = env::var("HOME").unwrap_or_default();
+            let dir = format!("{home}/Library/Application Support/KC-Overlay");
+            (dir.clone(), dir)
+        }
+        _ => {
+            let home = util::get_home_dir();
+            // Variáveis vazias ou com caminho relativo devem ser ignoradas.
+            let xdg_dir = |variable: &str, default: &str| {
+                env::var(variable)
+                    .ok()
+                    .filter(|x| Path::new(x).is_absolute())
+                    .unwrap_or_else(|| format!("{home}/{default}"))
+            };
+            (
+                format!("{}/kc-overlay", xdg_dir("XDG_CONFIG_HOME", ".config")),
+                format!("{}/kc-overlay", xdg_dir("XDG_DATA_HOME", ".local/share")),
+            )
+        }
+    }
+}
+
+pub fn config_dir() -> String {
+    paths().config_dir.clone()
+}
+
+// Jogadores conhecidos e histórico de stats.
+pub fn data_dir() -> String {
+    paths().data_dir.clone()
+}
+
+pub fn config_file() -> String {
+    match &paths().config_file {
+        Some(config_file) => config_file.clone(),
+        None => format!("{}/kc_overlay_config.json", config_dir()),
+    }
+}
+
+// Cria as pastas e move os arquivos das versões antigas, que ficavam na pasta .minecraft.
+// Executado antes de qualquer leitura.
+pub fn setup() {
+    let config_file = config_file();
+    let config_file_dir = Path::new(&config_file).parent().unwrap_or(Path::new(""));
+    for dir in [
+        PathBuf::from(config_dir()),
+        PathBuf::from(data_dir()),
+        config_file_dir.to_path_buf(),
+    ] {
+        if let Err(e) = fs::create_dir_all(&dir) {
+            println!("Failed to create {}: {e}", dir.display());
+        }
+    }
+
+    // As versões antigas não rodavam no macOS, então não há arquivos para mover.
+    if env::consts::OS == "macos" {
+        return;
+    }
+
+    let mut files = vec![
+        ("kc_overlay_modes.json", config_dir()),
+        ("kc_overlay_players.json", data_dir()),
+        ("kc_overlay_stats.db", data_dir()),
+    ];
+    // Com `--config` o arquivo antigo fica onde está, para as execuções sem a opção.
+    if paths().config_file.is_none() {
+        files.push(("kc_overlay_config.json", config_dir()));
+        files.push(("kc_overlay_config.json.bak", config_dir()));
+        files.push(("kc_overlay_config.json.invalid", config_dir()));
+    }
+
+    let minecraft_dir = util::get_minecraft_dir();
+    for (file, dir) in files {
+        let old_path = format!("{minecraft_dir}/{file}");
+        let new_path = format!("{dir}/{file}");
+        if !Path::new(&old_path).exists() || Path::new(&new_path).exists() {
+            continue;
+        }
+
+        // Renomear falha entre discos diferentes, nesse caso copia e apaga.
+        let result = fs::rename(&old_path, &new_path).or_else(|_| {
+            fs::copy(&old_path, &new_path)?;
+            fs::remove_file(&old_path)
+        });
+        match result {
+            Ok(_) => println!("Moved {old_path} to {new_path}"),
+            Err(e) => println!("Failed to move {old_path} to {new_path}: {e}"),
+        }
+    }
+}
@@ -1,14 +1,22 @@
 use iced::{
-    futures::{channel::mpsc, future, SinkExt, Stream},
+    futures::{channel::mpsc, stream::FuturesUnordered, SinkExt, Stream, StreamExt},
     stream,
 };
-use reqwest::Client;
+use rand::Rng;
+use reqwest::{Client, StatusCode};
 use serde_json::Value;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    time::{Duration, Instant},
+};
+use tokio::time::sleep;
 
 use crate::{
-    stats::{Bedwars, Stats, StatsType},
+    encounters::Encounter,
+    minecraft_text::{self, MinecraftText, TextStyle},
+    snapshots::Snapshot,
+    stats::{parse_level_badge, ratio, Bedwars, Duels, Game, Skywars, Stats, StatsType},
     util::Rgb,
     PlayerSender,
 };
@@ -16,175 +24,468 @@ use crate::{
 // Estrutura dos stats de um player
 #[derive(Debug, Clone)]
 pub struct Player {
+    pub uuid: Option<String>,
     pub username: String,
+    pub former_usernames: Vec<String>,
     pub username_color: Rgb,
-    pub clan: Option<String>,
-    pub clan_color: Rgb,
-    pub is_nicked: bool,
-    pub is_possible_cheater: bool,
+    // Nome do rank, mostrado antes do nome como no chat do jogo.
+    pub rank: Option<MinecraftText>,
+    // Tag do clan, pode ter códigos de cor.
+    pub clan: Option<MinecraftText>,
+    pub status: PlayerStatus,
+    // Regras de cheater que dispararam para o jogador, vazio se nenhuma disparou.
+    pub cheater_flags: Vec<String>,
     pub account_creation: i64,
     pub last_login: i64,
     pub is_connected: bool,
-    pub stats: Stats,
+    pub stats: HashMap<StatsType, Stats>,
+    // Última vez que o overlay viu o jogador antes desta consulta.
+    pub last_seen: Option<i64>,
+    // Último snapshot salvo de cada modo antes desta consulta, por id do modo.
+    pub last_snapshots: HashMap<String, Snapshot>,
+    // Encontros anteriores com o jogador, do mais recente para o mais antigo.
+    pub encounters: Vec<Encounter>,
+    // Resposta completa da API, só no jogador pesquisado, para mostrar os campos do perfil.
+    pub raw_json: Value,
 }
 
 // Funções para construir uma estrutura de player
 impl Player {
     pub fn new(
+        uuid: Option<String>,
         username: String,
         username_color: Rgb,
-        clan: Option<String>,
-        clan_color: Rgb,
-        is_possible_cheater: bool,
+        rank: Option<MinecraftText>,
+        clan: Option<MinecraftText>,
         account_creation: i64,
         last_login: i64,
         is_connected: bool,
-        stats: Stats,
+        stats: HashMap<StatsType, Stats>,
     ) -> Self {
         Player {
+            uuid,
             username,
+            former_usernames: vec![],
             username_color,
+            rank,
             clan,
-            clan_color,
-            is_nicked: false,
-            is_possible_cheater,
+            status: PlayerStatus::Found,
+            cheater_flags: vec![],
             account_creation,
             last_login,
             is_connected,
             stats,
+            last_seen: None,
+            last_snapshots: HashMap::new(),
+            encounters: vec![],
+            raw_json: Value::Null,
         }
     }
 
-    pub fn new_nicked(username: String, stats_type: StatsType) -> Self {
-        let stats = match stats_type {
-            StatsType::BedwarsAll
-            | StatsType::BedwarsSolo
-            | StatsType::BedwarsDoubles
-            | StatsType::BedwarsTrios
-            | StatsType::BedwarsQuads => Stats::Bedwars(crate::stats::Bedwars {
-                level: 999,
-                level_symbol: "?".to_string(),
-                winstreak: 0,
-                winrate: 0.,
-                final_kill_death_ratio: 0.,
-                kill_death_ratio: 0.,
-                level_color: Rgb::new(0, 255, 255),
-                wins: 0,
-                losses: 0,
-                kills: 0,
-                deaths: 0,
-                final_kills: 0,
-                final_deaths: 0,
-                hours_played: 0,
-                assists: 0,
-            }),
-        };
+    // Jogador sem stats: nicked, inexistente ou que a API não conseguiu responder.
+    pub fn unavailable(username: String, status: PlayerStatus) -> Self {
         Player {
+            uuid: None,
             username,
+            former_usernames: vec![],
             username_color: Rgb::new(0, 255, 255),
+            rank: None,
             clan: None,
-            clan_color: Rgb::new(0, 0, 0),
-            is_nicked: true,
-            is_possible_cheater: false,
-            stats,
+            status,
+            cheater_flags: vec![],
+            stats: HashMap::new(),
             account_creation: 0,
             last_login: 0,
             is_connected: true,
+            last_seen: None,
+            last_snapshots: HashMap::new(),
+            encounters: vec![],
+            raw_json: Value::Null,
+        }
+    }
+
+    // Stats de um modo específico. Todos os modos são preenchidos ao consultar a API.
+    pub fn stats(&self, stats_type: &StatsType) -> Option<&Stats> {
+        self.stats.get(stats_type)
+    }
+
+    // Nome colorido com a cor do rank e o rank antes, ex: [VIP] Jogador.
+    pub fn name_text(&self) -> MinecraftText {
+        let mut name_text = match &self.rank {
+            Some(rank) => {
+                let mut rank = rank.clone();
+                rank.push(" ", &TextStyle::new(self.username_color.clone()));
+                rank
+            }
+            None => MinecraftText::default(),
+        };
+        name_text.append(MinecraftText::plain(
+            &self.username,
+            self.username_color.clone(),
+        ));
+        name_text
+    }
+
+    // Tag do clan entre colchetes, ex: [KC].
+    pub fn clan_text(&self) -> MinecraftText {
+        let Some(clan) = &self.clan else {
+            return MinecraftText::default();
+        };
+        let bracket_color = clan
+            .spans
+            .first()
+            .map(|span| span.style.color.clone())
+            .unwrap_or(Rgb::new(255, 255, 255));
+        let mut clan_text = MinecraftText::plain("[", bracket_color.clone());
+        clan_text.append(clan.clone());
+        clan_text.append(MinecraftText::plain("]", bracket_color));
+        clan_text
+    }
+
+    pub fn is_possible_cheater(&self) -> bool {
+        !self.cheater_flags.is_empty()
+    }
+}
+
+// Situação do jogador na API do Mush.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayerStatus {
+    Found,
+    // Está na sala mas a API não conhece o nome, então está usando um nick.
+    Nicked,
+    // Nome pesquisado que não existe (erro de digitação ou conta apagada).
+    NotFound,
+    // A API respondeu com um erro.
+    Error(String),
+}
+
+// Estado de um jogador que ainda não apareceu na lista.
+#[derive(Debug, Clone)]
+pub enum LookupState {
+    Loading,
+    Retrying(u32),
+    Failed,
+}
+
+// Configuração das tentativas de consulta à API.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub timeout: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            timeout: Duration::from_secs(8),
+        }
+    }
+}
+
+impl RetryPolicy {
+    // Backoff exponencial (500ms, 1s, 2s...) limitado a 8 segundos, com jitter para não
+    // disparar todas as tentativas ao mesmo tempo.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let max_millis = (500u64 << attempt.saturating_sub(1).min(4)).min(8000);
+        let millis = rand::thread_rng().gen_range(max_millis / 2..=max_millis);
+        Duration::from_millis(millis)
+    }
+}
+
+// Erros possíveis ao consultar a API do Mush.
+#[derive(Debug, Clone)]
+pub enum FetchError {
+    // Falha temporária (timeout, conexão ou erro 5xx), vale a pena tentar de novo.
+    Retryable(String),
+    // Falha que não vai se resolver tentando de novo.
+    Fatal(String),
+    // A API pediu para esperarmos antes de consultar novamente.
+    RateLimited,
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::Retryable(e) => write!(f, "{e}"),
+            FetchError::Fatal(e) => write!(f, "{e}"),
+            FetchError::RateLimited => write!(f, "limite de consultas atingido"),
+        }
+    }
+}
+
+const MUSH_API: &str = "https://mush.com.br/api/player/";
+
+// Resposta da API junto com o status HTTP e quantas consultas ainda podemos fazer.
+struct ApiResponse {
+    status: StatusCode,
+    json: Value,
+    rate_limit_remaining: Option<i64>,
+}
+
+// Faz uma única consulta à API e classifica o erro, caso aconteça.
+async fn fetch_player_json(
+    client: &Client,
+    username: &str,
+    timeout: Duration,
+) -> Result<ApiResponse, FetchError> {
+    let response = match client
+        .get(format!("{MUSH_API}{username}"))
+        .timeout(timeout)
+        .send()
+        .await
+    {
+        Ok(ok) => ok,
+        Err(e) if e.is_timeout() || e.is_connect() => {
+            return Err(FetchError::Retryable(e.to_string()))
+        }
+        Err(e) => return Err(FetchError::Fatal(e.to_string())),
+    };
+
+    let rate_limit_remaining = response
+        .headers()
+        .get("x-ratelimit-remaining")
+        .and_then(|x| x.to_str().ok())
+        .map(|x| x.parse::<i64>().unwrap_or(0));
+    if response.status() == StatusCode::TOO_MANY_REQUESTS {
+        return Err(FetchError::RateLimited);
+    }
+    if response.status().is_server_error() {
+        return Err(FetchError::Retryable(format!(
+            "a API respondeu com {}",
+            response.status()
+        )));
+    }
+
+    let status = response.status();
+    let text = match response.text().await {
+        Ok(ok) => ok,
+        Err(e) => return Err(FetchError::Retryable(e.to_string())),
+    };
+
+    match serde_json::from_str(&text) {
+        Ok(json) => Ok(ApiResponse {
+            status,
+            json,
+            rate_limit_remaining,
+        }),
+        Err(e) => Err(FetchError::Fatal(e.to_string())),
+    }
+}
+
+// Consulta a API, tentando novamente em erros temporários até esgotar as tentativas. O limite de
+// consultas retorna na hora, quem chamou espera o limite renovar. `on_retry` é chamado com o
+// número da tentativa antes de cada espera.
+async fn fetch_with_retry(
+    client: &Client,
+    username: &str,
+    policy: RetryPolicy,
+    mut on_retry: impl FnMut(u32),
+) -> Result<ApiResponse, FetchError> {
+    let mut attempt = 0;
+    loop {
+        match fetch_player_json(client, username, policy.timeout).await {
+            Err(e @ FetchError::Retryable(_)) if attempt < policy.max_retries => {
+                attempt += 1;
+                println!(
+                    "{username}: {e}. Tentando novamente ({attempt}/{})...",
+                    policy.max_retries
+                );
+                on_retry(attempt);
+                sleep(policy.backoff(attempt)).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+// De onde veio o nome consultado.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LookupKind {
+    // Nome da sala, onde um jogador que a API não encontra só pode estar usando nick.
+    Lobby,
+    // Nome pesquisado na tela "Ver jogador", que guarda a resposta completa da API.
+    Search,
+    // Stats do próprio jogador.
+    Session,
+}
+
+// Monta o player a partir da resposta da API.
+fn player_from_response(username: String, response: ApiResponse, kind: LookupKind) -> Player {
+    if response.json["success"].as_bool().unwrap_or(false) {
+        let mut player = get_player_data(username, &response.json["response"]);
+        if kind == LookupKind::Search {
+            player.raw_json = response.json["response"].clone();
+        }
+        return player;
+    }
+
+    let message = response.json["error"]
+        .as_str()
+        .or(response.json["message"].as_str())
+        .or(response.json["response"]["message"].as_str())
+        .unwrap_or("")
+        .to_string();
+    let lowercase_message = message.to_lowercase();
+    let not_found = response.status == StatusCode::NOT_FOUND
+        || response.json["error_code"].as_i64() == Some(404)
+        || lowercase_message.contains("not found")
+        || lowercase_message.contains("não encontrado");
+
+    let status = if not_found && kind == LookupKind::Lobby {
+        PlayerStatus::Nicked
+    } else if not_found {
+        PlayerStatus::NotFound
+    } else if message.is_empty() {
+        PlayerStatus::Error(format!("a API respondeu com {}", response.status))
+    } else {
+        PlayerStatus::Error(message)
+    };
+    Player::unavailable(username, status)
+}
+
+// Controla quantas consultas podem estar em andamento ao mesmo tempo. Aumenta aos poucos
+// enquanto a API responde rápido e corta pela metade quando ela fica lenta.
+struct ConcurrencyLimiter {
+    limit: usize,
+    max: usize,
+    average_latency: Option<Duration>,
+}
+
+impl ConcurrencyLimiter {
+    fn new(max: usize) -> Self {
+        let max = max.max(1);
+        ConcurrencyLimiter {
+            limit: max.min(4),
+            max,
+            average_latency: None,
         }
     }
+
+    fn record(&mut self, latency: Duration, rate_limit_remaining: Option<i64>) {
+        let average_latency = match self.average_latency {
+            Some(average) => average.mul_f64(0.8) + latency.mul_f64(0.2),
+            None => latency,
+        };
+
+        if latency > average_latency * 2 {
+            self.limit = (self.limit / 2).max(1);
+        } else if latency <= average_latency {
+            self.limit = (self.limit + 1).min(self.max);
+        }
+        self.average_latency = Some(average_latency);
+
+        // Nunca deixa mais consultas em andamento do que a API ainda aceita.
+        if let Some(remaining) = rate_limit_remaining {
+            self.limit = self.limit.min(remaining.max(1) as usize);
+        }
+    }
+}
+
+// Diz se a API não aceita mais consultas por enquanto, seja pelo erro 429 ou porque o
+// `x-ratelimit-remaining` chegou a zero. Vale para a lista da sala e para as consultas avulsas.
+fn rate_limit_reached(result: &Result<ApiResponse, FetchError>) -> bool {
+    let reached = match result {
+        Ok(response) => response.rate_limit_remaining.is_some_and(|x| x < 1),
+        Err(FetchError::RateLimited) => true,
+        Err(_) => false,
+    };
+    if reached {
+        println!("Esperar até podermos consultar a API novamente.");
+    }
+    reached
+}
+
+// Resultado de uma consulta feita por `get_players`.
+struct Lookup {
+    result: PlayerSender,
+    rate_limit_remaining: Option<i64>,
+    rate_limited: bool,
+}
+
+async fn lookup_player(
+    client: Client,
+    player_name: String,
+    policy: RetryPolicy,
+    mut retry_output: mpsc::Sender<PlayerSender>,
+) -> (Lookup, Duration) {
+    let start = Instant::now();
+
+    let result = fetch_with_retry(&client, &player_name, policy, |attempt| {
+        let _ = retry_output.try_send(PlayerSender::Retrying(player_name.clone(), attempt));
+    })
+    .await;
+
+    let rate_limited = rate_limit_reached(&result);
+    let lookup = match result {
+        Ok(response) => Lookup {
+            rate_limit_remaining: response.rate_limit_remaining,
+            result: PlayerSender::Player(Box::new(player_from_response(
+                player_name,
+                response,
+                LookupKind::Lobby,
+            ))),
+            rate_limited,
+        },
+        Err(e) => {
+            println!("Falha ao obter {player_name}: {e}\n Pulando.");
+            Lookup {
+                result: PlayerSender::Failed(player_name),
+                rate_limit_remaining: None,
+                rate_limited,
+            }
+        }
+    };
+
+    (lookup, start.elapsed())
 }
 
 // Pega os stats dos players da API do Mush.
 pub fn get_players(
+    client: Client,
     str_player_list: Vec<String>,
-    stats_type: StatsType,
+    policy: RetryPolicy,
+    max_in_flight: usize,
 ) -> impl Stream<Item = PlayerSender> {
-    stream::channel(100, |mut output| async move {
+    stream::channel(100, move |mut output| async move {
         let (sender, mut receiver) = mpsc::channel(100);
 
         output.send(PlayerSender::Sender(sender)).await.unwrap();
-        let client = Client::new();
-        const MUSH_API: &str = "https://mush.com.br/api/player/";
 
         let mut interrupted = false;
-        let rate_limited_arc = Arc::new(Mutex::new(false));
-
-        // Processa jogadores em grupos de 4
-        for chunk in str_player_list.chunks(4) {
-            let mut futures = Vec::new();
-
-            // Cria futures para cada jogador no grupo
-            for player_name in chunk {
-                let client = client.clone();
-                let stats_type = stats_type.clone();
-                let url = format!("{}{}", MUSH_API, player_name);
-
-                let rate_limited = Arc::clone(&rate_limited_arc);
-
-                futures.push(async move {
-                    let request = match client.get(url).send().await {
-                        Ok(response) => {
-                            let rate_limit = response.headers().get("x-ratelimit-remaining").unwrap().to_str().unwrap().parse().unwrap_or(0);
-                            if rate_limit < 1{
-                                let mut rate_limited = rate_limited.lock().await;
-                                *rate_limited = true;
-                                println!("Esperar até podermos consultar a API novamente.");
-                                return None;
-                            }
-                            match response.text().await {
-                            Ok(ok) => ok,
-                            Err(e) => {
-                                println!("Falha ao obter texto da resposta da API para {player_name}: {e}\n Pulando.");
-                                return None;
-                            }
-                        }
-                    },
-                        Err(e) => {
-                            println!("Falha ao obter resposta para {player_name}: {e}\n Pulando.");
-                            return None;
-                        }
-                    };
-
-                    let json: Value = match serde_json::from_str(&request) {
-                        Ok(ok) => ok,
-                        Err(e) => {
-                            println!("{player_name}: {e}");
-                            return None;
-                        }
-                    };
-
-                    if !json["success"].as_bool().unwrap() {
-                        return Some(Player::new_nicked(player_name.to_string(), stats_type));
-                    }
-
-                    let response = json["response"].clone();
-                    Some(get_player_data(player_name.to_string(), response, stats_type))
-                });
-
-                let rate_limited_outer = Arc::clone(&rate_limited_arc);
-
-                if *rate_limited_outer.lock().await {
+        let mut rate_limited = false;
+        let mut limiter = ConcurrencyLimiter::new(max_in_flight);
+        let mut queue = str_player_list.into_iter();
+        let mut in_flight = FuturesUnordered::new();
+
+        loop {
+            // Inicia novas consultas enquanto houver espaço.
+            while !rate_limited && in_flight.len() < limiter.limit {
+                let Some(player_name) = queue.next() else {
                     break;
-                }
+                };
+                in_flight.push(lookup_player(
+                    client.clone(),
+                    player_name,
+                    policy,
+                    output.clone(),
+                ));
             }
 
-            // Executa o grupo de futures concorrentemente
-            let results = future::join_all(futures).await;
-
-            // Envia resultados para thread principal
-            for player in results.into_iter().flatten() {
-                if receiver.try_next().is_ok() {
-                    interrupted = true;
-                    break;
-                }
-                output.send(PlayerSender::Player(player)).await.unwrap();
-            }
-
-            if interrupted || *rate_limited_arc.lock().await {
+            // Envia cada resultado para a thread principal assim que ele chega.
+            let Some((lookup, latency)) = in_flight.next().await else {
+                break;
+            };
+            if receiver.try_next().is_ok() {
+                interrupted = true;
                 break;
             }
+
+            limiter.record(latency, lookup.rate_limit_remaining);
+            rate_limited |= lookup.rate_limited;
+            output.send(lookup.result).await.unwrap();
         }
-        if *rate_limited_arc.lock().await {
+        if rate_limited {
             output.send(PlayerSender::WaitOrder).await.unwrap()
         }
         if !interrupted {
@@ -193,201 +494,144 @@ pub fn get_players(
     })
 }
 
-// Coleta os stats de apenas um jogador.
-pub async fn get_player(username: &str, stats_type: StatsType) -> Result<Player, ()> {
-    let client = Client::new();
-    let url = "https://mush.com.br/api/player/".to_string() + username;
+// Progresso da consulta de um único jogador.
+#[derive(Debug, Clone)]
+pub enum PlayerLookup {
+    Retrying(u32),
+    // A API não aceita mais consultas, a interface deve esperar como na lista da sala.
+    RateLimited,
+    Done(Result<Box<Player>, ()>),
+}
 
-    let request = match client.get(url).send().await {
-        Ok(response) => match response.text().await {
-            Ok(ok) => ok,
+// Coleta os stats de apenas um jogador, avisando cada nova tentativa e o limite de consultas.
+pub fn get_player(
+    client: Client,
+    username: String,
+    policy: RetryPolicy,
+    kind: LookupKind,
+) -> impl Stream<Item = PlayerLookup> {
+    stream::channel(100, move |mut output| async move {
+        println!("Getting {username} stats...");
+
+        let mut retry_output = output.clone();
+        let result = fetch_with_retry(&client, &username, policy, |attempt| {
+            let _ = retry_output.try_send(PlayerLookup::Retrying(attempt));
+        })
+        .await;
+
+        if rate_limit_reached(&result) {
+            output.send(PlayerLookup::RateLimited).await.unwrap();
+        }
+        let result = match result {
+            Ok(response) => Ok(Box::new(player_from_response(username, response, kind))),
             Err(e) => {
-                println!("Failed to get text of {username}'s API response: {e}\n Skipping.");
-                return Err(());
+                println!("Failed to get {username} stats: {e}\n Skipping.");
+                Err(())
             }
-        },
-        Err(e) => {
-            println!("Failed to get {username} response: {e}\n Skipping.");
-            return Err(());
-        }
-    };
-
-    println!("Getting {username} stats...");
-
-    let json: Value = match serde_json::from_str(&request) {
-        Ok(ok) => ok,
-        Err(e) => {
-            println!("{username}: {e}");
-            return Err(());
-        }
-    };
-
-    if !json["success"].as_bool().unwrap() {
-        return Ok(Player::new_nicked(username.to_owned(), stats_type));
-    }
-    let response = json["response"].clone();
-
-    Ok(get_player_data(username.to_owned(), response, stats_type))
+        };
+        output.send(PlayerLookup::Done(result)).await.unwrap();
+    })
 }
 
-fn get_player_data(username: String, response: Value, stats_type: StatsType) -> Player {
-    let is_possible_cheater = response["last_login"].as_i64().unwrap()
-        - response["first_login"].as_i64().unwrap()
-        < 7200000;
-
-    let username_color = response["rank_tag"]["color"].as_str().unwrap();
-    let (clan, clan_color) = if response["clan"].is_object() {
-        (
-            Some(response["clan"]["tag"].as_str().unwrap().to_string()),
-            response["clan"]["tag_color"].as_str().unwrap(),
-        )
-    } else {
-        (None, "#ffffff")
-    };
+fn get_player_data(username: String, response: &Value) -> Player {
+    let uuid = response["unique_id"]
+        .as_str()
+        .or(response["uuid"].as_str())
+        .map(String::from);
+    let username_color = response["rank_tag"]["color"]
+        .as_str()
+        .and_then(minecraft_text::parse_color)
+        .unwrap_or(Rgb::new(170, 170, 170));
+    // Membros sem rank vêm sem nome ou com o nome vazio.
+    let rank = response["rank_tag"]["name"]
+        .as_str()
+        .or(response["rank_tag"]["prefix"].as_str())
+        .filter(|name| !name.trim().is_empty())
+        .map(|name| {
+            let name = name.trim();
+            if name.starts_with('[') {
+                MinecraftText::parse(name, username_color.clone())
+            } else {
+                MinecraftText::parse(&format!("[{name}]"), username_color.clone())
+            }
+        });
+    let clan = response["clan"]["tag"].as_str().map(|tag| {
+        let clan_color = response["clan"]["tag_color"]
+            .as_str()
+            .and_then(minecraft_text::parse_color)
+            .unwrap_or(Rgb::new(255, 255, 255));
+        MinecraftText::parse(tag, clan_color)
+    });
 
     let account_creation = response["first_login"].as_i64().unwrap();
     let last_login = response["last_login"].as_i64().unwrap();
     let is_connected = response["connected"].as_bool().unwrap();
 
-    let stats = match stats_type {
-        StatsType::BedwarsAll
-        | StatsType::BedwarsSolo
-        | StatsType::BedwarsDoubles
-        | StatsType::BedwarsTrios
-        | StatsType::BedwarsQuads => {
-            let bedwars_stats = response["stats"]["bedwars"].clone();
-            let level = if !is_possible_cheater {
-                bedwars_stats["level"].as_i64().unwrap_or(0)
-            } else {
-                998
-            };
-            let level_symbol_raw: String = bedwars_stats["level_badge"]["format"]
-                .as_str()
-                .unwrap()
-                .to_string();
-
-            let level_symbol = level_symbol_raw
-                .chars()
-                .find(|c| {
-                    !c.is_ascii_alphanumeric()
-                        && !c.is_ascii_whitespace()
-                        && !c.is_ascii_punctuation()
-                })
-                .unwrap()
-                .to_string();
-
-            let level_color = level_symbol_raw.chars().nth(1).unwrap();
-
-            let (
-                ws_entry,
-                wins_entry,
-                losses_entry,
-                kills_entry,
-                deaths_entry,
-                final_kills_entry,
-                final_deaths_entry,
-                assists_entry,
-                hours_played_entry,
-            ) = match stats_type {
-                StatsType::BedwarsAll => (
-                    "winstreak",
-                    "wins",
-                    "losses",
-                    "kills",
-                    "deaths",
-                    "final_kills",
-                    "final_deaths",
-                    "assists",
-                    "bedwars",
-                ),
-                StatsType::BedwarsSolo => (
-                    "solo_winstreak",
-                    "solo_wins",
-                    "solo_losses",
-                    "solo_kills",
-                    "solo_deaths",
-                    "solo_final_kills",
-                    "solo_final_deaths",
-                    "solo_assists",
-                    "bedwars_solo",
-                ),
-                StatsType::BedwarsDoubles => (
-                    "doubles_winstreak",
-                    "doubles_wins",
-                    "doubles_losses",
-                    "doubles_kills",
-                    "doubles_deaths",
-                    "doubles_final_kills",
-                    "doubles_final_deaths",
-                    "doubles_assists",
-                    "bedwars_doubles",
-                ),
-                StatsType::BedwarsTrios => (
-                    "3v3v3v3_winstreak",
-                    "3v3v3v3_wins",
-                    "3v3v3v3_losses",
-                    "3v3v3v3_kills",
-                    "3v3v3v3_deaths",
-                    "3v3v3v3_final_kills",
-                    "3v3v3v3_final_deaths",
-                    "3v3v3v3_assists",
-                    "bedwars_3v3v3v3",
-                ),
-                StatsType::BedwarsQuads => (
-                    "4v4v4v4_winstreak",
-                    "4v4v4v4_wins",
-                    "4v4v4v4_losses",
-                    "4v4v4v4_kills",
-                    "4v4v4v4_deaths",
-                    "4v4v4v4_final_kills",
-                    "4v4v4v4_final_deaths",
-                    "4v4v4v4_assists",
-                    "bedwars_4v4v4v4",
-                ),
-                //_ => panic!("Impossível!"),
-            };
-
-            let winstreak = bedwars_stats[ws_entry].as_i64().unwrap_or(0) as i32;
-
-            let mut winrate = bedwars_stats[wins_entry].as_i64().unwrap_or(0) as f32
-                / bedwars_stats[losses_entry].as_i64().unwrap_or(0) as f32;
-            let mut final_kill_death_ratio = bedwars_stats[final_kills_entry].as_i64().unwrap_or(0)
-                as f32
-                / bedwars_stats[final_deaths_entry].as_i64().unwrap_or(0) as f32;
-
-            let mut kill_death_ratio = bedwars_stats[kills_entry].as_i64().unwrap_or(0) as f32
-                / bedwars_stats[deaths_entry].as_i64().unwrap_or(0) as f32;
+    // Todos os modos vêm na mesma resposta, então guardamos todos de uma vez.
+    let stats = StatsType::get_stats_list()
+        .into_iter()
+        .map(|stats_type| {
+            let stats = get_stats(response, &stats_type);
+            (stats_type, stats)
+        })
+        .collect();
 
-            if winrate.is_nan() || winrate.is_infinite() {
-                winrate = 0.0;
-            }
-            if final_kill_death_ratio.is_nan() || final_kill_death_ratio.is_infinite() {
-                final_kill_death_ratio = 0.0;
-            }
-            if kill_death_ratio.is_nan() || kill_death_ratio.is_infinite() {
-                kill_death_ratio = 0.0;
-            }
+    Player::new(
+        uuid,
+        username,
+        username_color,
+        rank,
+        clan,
+        account_creation,
+        last_login,
+        is_connected,
+        stats,
+    )
+}
 
-            let wins = bedwars_stats[wins_entry].as_u64().unwrap_or(0);
-            let losses = bedwars_stats[losses_entry].as_u64().unwrap_or(0);
-            let kills = bedwars_stats[kills_entry].as_u64().unwrap_or(0);
-            let deaths = bedwars_stats[deaths_entry].as_u64().unwrap_or(0);
-            let final_kills = bedwars_stats[final_kills_entry].as_u64().unwrap_or(0);
-            let final_deaths = bedwars_stats[final_deaths_entry].as_u64().unwrap_or(0);
-            let assists = bedwars_stats[assists_entry].as_u64().unwrap_or(0);
-            let hours_played = response["stats"]["play_time"][hours_played_entry]
-                .as_u64()
-                .unwrap_or(1)
-                / 3600;
+// Extrai os stats de um modo a partir da resposta da API.
+fn get_stats(response: &Value, stats_type: &StatsType) -> Stats {
+    // As chaves de cada modo vêm da tabela de modos (assets/modes.json).
+    let mode = stats_type.definition();
+    let mode_stats = &response["stats"][mode.section.as_str()];
+    let get = |stat: &str| mode_stats[mode.key(stat).as_str()].as_u64().unwrap_or(0);
+    let level_key = mode
+        .keys
+        .get("level")
+        .map(String::as_str)
+        .unwrap_or("level");
+
+    match mode.game {
+        Game::Bedwars => {
+            let level = mode_stats[level_key].as_i64().unwrap_or(0);
+            let badge_format = mode_stats["level_badge"]["format"]
+                .as_str()
+                .unwrap_or("§7[✫]");
+
+            let wins = get("wins");
+            let losses = get("losses");
+            let kills = get("kills");
+            let deaths = get("deaths");
+            let final_kills = get("final_kills");
+            let final_deaths = get("final_deaths");
+            let assists = get("assists");
+            let hours_played = match &mode.play_time {
+                Some(play_time) => {
+                    response["stats"]["play_time"][play_time.as_str()]
+                        .as_u64()
+                        .unwrap_or(1)
+                        / 3600
+                }
+                None => 0,
+            };
 
             Stats::Bedwars(Bedwars {
                 level: level as i32,
-                level_symbol,
-                winstreak,
-                winrate,
-                final_kill_death_ratio,
-                kill_death_ratio,
-                level_color: Rgb::from_minecraft_color(&level_color),
+                badge: parse_level_badge(badge_format, level),
+                winstreak: get("winstreak") as i32,
+                winrate: ratio(wins, losses) as f32,
+                final_kill_death_ratio: ratio(final_kills, final_deaths) as f32,
+                kill_death_ratio: ratio(kills, deaths) as f32,
                 wins,
                 losses,
                 kills,
@@ -398,17 +642,34 @@ fn get_player_data(username: String, response: Value, stats_type: StatsType) ->
                 assists,
             })
         }
-    };
-
-    Player::new(
-        username,
-        Rgb::from_hex(username_color),
-        clan,
-        Rgb::from_hex(clan_color),
-        is_possible_cheater,
-        account_creation,
-        last_login,
-        is_connected,
-        stats,
-    )
+        Game::Skywars => {
+            let wins = get("wins");
+            let losses = get("losses");
+            let kills = get("kills");
+            let deaths = get("deaths");
+
+            Stats::Skywars(Skywars {
+                level: mode_stats[level_key].as_i64().unwrap_or(0) as i32,
+                winstreak: get("winstreak") as i32,
+                winrate: ratio(wins, losses) as f32,
+                kill_death_ratio: ratio(kills, deaths) as f32,
+                wins,
+                losses,
+                kills,
+                deaths,
+            })
+        }
+        Game::Duels => {
+            let wins = get("wins");
+            let losses = get("losses");
+
+            Stats::Duels(Duels {
+                winstreak: get("winstreak") as i32,
+                best_winstreak: get("best_winstreak") as i32,
+                winrate: ratio(wins, losses) as f32,
+                wins,
+                losses,
+            })
+        }
+    }
 }
@@ -2,16 +2,25 @@
 
 use iced::{
     theme,
-    widget::{column, container, row, text, Column},
-    Alignment, Color, Font, Length, Renderer,
+    widget::{column, container, row, scrollable, text, Column, Row, Text},
+    Alignment, Color, Element, Length, Renderer,
 };
 
+use serde_json::Value;
+
 use crate::{
-    stats::StatsType,
+    cheater::{CheaterRule, RuleParameter},
+    color_tiers::{self, ColorTiers},
+    config,
+    encounters::Encounter,
+    player::{LookupState, PlayerStatus},
+    stats::{self, Bedwars, Skywars, Stats, StatsType},
     themed_widgets::{
-        button, pick_list, red_button, secondary_button, slider, text_input, toggler,
+        button, invalid_text_input, minecraft_text, pick_list, red_button, secondary_button,
+        slider, text_input, toggler, tooltip,
     },
-    util, Message, MineClient,
+    threat::{self, ThreatWeight},
+    util, Message, MineClient, SearchedPlayer,
 };
 
 #[derive(Clone, Copy, Default, Debug)]
@@ -22,6 +31,14 @@ pub enum Screen {
     Welcome,
     Info,
     ViewPlayer,
+    Threat,
+    ColorTiers,
+    Columns,
+    Snapshots,
+    PlayerHistory,
+    Session,
+    Encounters,
+    CheaterRules,
 }
 
 pub fn get_screen(
@@ -50,94 +67,180 @@ pub fn get_screen(
 
             let screen_title_widget = text(screen_title_text);
 
-            let bar = row![screen_title_widget];
+            let mut bar = row![screen_title_widget].spacing(15);
+
+            // Alerta de jogadores com índice de ameaça acima do limite.
+            let dangerous_players: Vec<String> = app
+                .players
+                .iter()
+                .filter(|player| match player.stats(&app.stats_type) {
+                    Some(stats) if player.status == PlayerStatus::Found => {
+                        app.config.threat.is_alert(app.config.threat.score(stats))
+                    }
+                    _ => false,
+                })
+                .map(|player| player.username.clone())
+                .collect();
+            if !dangerous_players.is_empty() {
+                bar = bar.push(
+                    text(format!("Perigo: {}", dangerous_players.join(", ")))
+                        .color(Color::from_rgb8(255, 85, 85)),
+                );
+            }
+
+            // As colunas de stats mudam de acordo com o modo selecionado.
+            let columns = app.stats_type.visible_columns(&app.config.extra_columns);
 
             let mut username_column = Column::new().width(300);
-            let mut winstreak_column = Column::new().align_x(Alignment::Center);
-            let mut winrate_column = Column::new().align_x(Alignment::Center);
-            let mut fkdr_column = Column::new().align_x(Alignment::Center);
-            let mut kdr_column = Column::new().align_x(Alignment::Center);
+            let mut stat_columns: Vec<Column<'static, Message, theme::Theme, Renderer>> = columns
+                .iter()
+                .map(|_| Column::new().align_x(Alignment::Center))
+                .collect();
+            let mut threat_column = Column::new().align_x(Alignment::Center);
 
             let players = app.players.clone();
 
-            if !players.is_empty() {
+            if !players.is_empty() || !app.pending_lookups.is_empty() {
                 username_column = username_column.push(text("Nome"));
-                winstreak_column = winstreak_column.push(text("WS"));
-                winrate_column = winrate_column.push(text("WLR"));
-                fkdr_column = fkdr_column.push(text("FKDR"));
-                kdr_column = kdr_column.push(text("KDR"));
+                stat_columns = push_row(
+                    stat_columns,
+                    columns
+                        .iter()
+                        .map(|column| text(column.header.clone()).into())
+                        .collect(),
+                );
+                threat_column = threat_column.push(text("Índice"));
             }
             for player in players {
-                let (
-                    level,
-                    level_symbol,
-                    level_color,
-                    winstreak,
-                    winrate,
-                    final_kill_death_ratio,
-                    kill_death_ratio,
-                ) = match player.stats {
-                    crate::stats::Stats::Bedwars(bedwars) => (
-                        bedwars.level,
-                        bedwars.level_symbol,
-                        bedwars.level_color,
-                        bedwars.winstreak,
-                        bedwars.winrate,
-                        bedwars.final_kill_death_ratio,
-                        bedwars.kill_death_ratio,
-                    ),
-                };
-                let clan = if let Some(value) = &player.clan {
-                    format!("[{}]", value)
-                } else {
-                    String::new()
+                let stats = player.stats(&app.stats_type).cloned();
+                let level_widget = match (&player.status, &stats) {
+                    (PlayerStatus::Nicked, _) => {
+                        row![text("[NICKED]").color(Color::from_rgb8(255, 255, 0))]
+                    }
+                    (PlayerStatus::NotFound, _) => row![text("[NÃO ENCONTRADO]")
+                        .color(Color::from_rgb8(170, 170, 170))
+                        .size(12)],
+                    (PlayerStatus::Error(_), _) => row![text("[ERRO NA API]")
+                        .color(Color::from_rgb8(237, 135, 150))
+                        .size(12)],
+                    (PlayerStatus::Found, Some(stats)) if player.is_possible_cheater() => {
+                        row![level_badge(stats), cheater_badge(&player.cheater_flags)].spacing(5)
+                    }
+                    (PlayerStatus::Found, Some(stats)) => level_badge(stats),
+                    (PlayerStatus::Found, None) => row![],
                 };
 
-                let level_widget = if player.is_nicked {
-                    row![text("[NICKED]").color(Color::from_rgb8(255, 255, 0))]
-                } else if player.is_possible_cheater {
-                    row![text("[possível CHEATER]")
-                        .color(Color::from_rgb8(255, 0, 0))
-                        .size(12)]
-                } else {
-                    row![
-                        text(format!("[{}", level)).color(level_color.to_color()),
-                        text(level_symbol)
-                            .font(Font::with_name("Noto Sans Symbols 2"))
-                            .color(level_color.to_color()),
-                        text("]").color(level_color.to_color())
-                    ]
+                let username_widget = minecraft_text(&player.name_text());
+                let clan_widget = minecraft_text(&player.clan_text());
+                // Snapshot da última vez que o jogador foi visto, para mostrar a mudança dos stats.
+                let last_snapshot = player
+                    .last_snapshots
+                    .get(app.stats_type.id())
+                    .filter(|_| app.config.show_deltas);
+                let values: Vec<Element<'static, Message, theme::Theme, Renderer>> =
+                    match (&player.status, &stats) {
+                        (PlayerStatus::Found, Some(stats)) => stats
+                            .column_values(&columns)
+                            .into_iter()
+                            .zip(&columns)
+                            .map(|(value, column)| {
+                                let current = stats.value(&column.stat);
+                                let color = current
+                                    .map(|x| app.config.color_tiers.color(&column.stat, x))
+                                    .unwrap_or(util::Rgb::new(255, 255, 255));
+                                let mut cell = row![text(value).color(color.to_color())]
+                                    .spacing(3)
+                                    .align_y(Alignment::Center);
+                                let previous = last_snapshot
+                                    .and_then(|snapshot| snapshot.values.get(&column.stat));
+                                if let (Some(current), Some(previous)) = (current, previous) {
+                                    if let Some(delta) =
+                                        delta_text(current - previous, column.decimals)
+                                    {
+                                        cell = cell.push(delta);
+                                    }
+                                }
+                                cell.into()
+                            })
+                            .collect(),
+                        (PlayerStatus::Nicked, _) => {
+                            columns.iter().map(|_| text("?").into()).collect()
+                        }
+                        _ => columns.iter().map(|_| text("-").into()).collect(),
+                    };
+                let threat_widget = match (&player.status, &stats) {
+                    (PlayerStatus::Found, Some(stats)) => {
+                        let score = app.config.threat.score(stats);
+                        text(format!("{:.0}", score)).color(threat::score_color(score).to_color())
+                    }
+                    (PlayerStatus::Nicked, _) => text("?"),
+                    _ => text("-"),
                 };
 
-                let username_widget = text(player.username).color(player.username_color.to_color());
-                let clan_widget = text(clan).color(player.clan_color.to_color());
-                let (winstreak_widget, winrate_widget, fkdr, kdr) = if player.is_nicked {
-                    (text("?"), text("?"), text("?"), text("?"))
-                } else {
-                    (
-                        text(format!("{}", winstreak)),
-                        text(format!("{:.2}", winrate)),
-                        text(format!("{:.2}", final_kill_death_ratio)),
-                        text(format!("{:.2}", kill_death_ratio)),
-                    )
-                };
+                let mut username_row = row![level_widget, username_widget, clan_widget].spacing(5);
+                if let Some(former_username) = player.former_usernames.last() {
+                    username_row = username_row.push(
+                        text(format!("(antes {former_username})"))
+                            .color(Color::from_rgb8(170, 170, 170))
+                            .size(12),
+                    );
+                }
+                // Quem foi visto há menos de uma hora provavelmente é da mesma sessão.
+                let last_seen = player
+                    .last_seen
+                    .filter(|x| chrono::Utc::now().timestamp_millis() - x > 3_600_000);
+                if let (true, Some(last_seen)) = (app.config.show_deltas, last_seen) {
+                    username_row = username_row.push(
+                        text(format!("(visto {})", util::time_ago(last_seen)))
+                            .color(Color::from_rgb8(170, 170, 170))
+                            .size(12),
+                    );
+                }
+                if !player.encounters.is_empty() {
+                    username_row = username_row.push(
+                        text(encounter_summary(&player.encounters))
+                            .color(Color::from_rgb8(170, 170, 170))
+                            .size(12),
+                    );
+                }
 
-                let username_row = row![level_widget, username_widget, clan_widget].spacing(5);
+                username_column = username_column.push(username_row);
+                stat_columns = push_row(stat_columns, values);
+                threat_column = threat_column.push(threat_widget);
+            }
+            // Jogadores que ainda estão sendo consultados ou cuja consulta falhou.
+            for (username, state) in &app.pending_lookups {
+                let state_widget = match state {
+                    LookupState::Loading => {
+                        text("carregando...").color(Color::from_rgb8(170, 170, 170))
+                    }
+                    LookupState::Retrying(attempt) => text(format!(
+                        "tentando novamente ({}/{})",
+                        attempt, app.retry_policy.max_retries
+                    ))
+                    .color(Color::from_rgb8(255, 255, 0)),
+                    LookupState::Failed => {
+                        text("falha ao consultar").color(Color::from_rgb8(237, 135, 150))
+                    }
+                };
+                let username_row = row![
+                    text(username.clone()).color(Color::from_rgb8(170, 170, 170)),
+                    state_widget.size(12)
+                ]
+                .spacing(5);
 
                 username_column = username_column.push(username_row);
-                winstreak_column = winstreak_column.push(winstreak_widget);
-                winrate_column = winrate_column.push(winrate_widget);
-                fkdr_column = fkdr_column.push(fkdr);
-                kdr_column = kdr_column.push(kdr)
+                stat_columns = push_row(
+                    stat_columns,
+                    columns.iter().map(|_| text("-").into()).collect(),
+                );
+                threat_column = threat_column.push(text("-"));
             }
-            let column_row = row![
-                username_column,
-                winstreak_column,
-                winrate_column,
-                fkdr_column,
-                kdr_column
-            ]
-            .spacing(15);
+            let mut column_row = row![username_column].spacing(15);
+            for stat_column in stat_columns {
+                column_row = column_row.push(stat_column);
+            }
+            column_row = column_row.push(threat_column);
             let container = container(column_row);
 
             let settings =
@@ -148,7 +251,9 @@ pub fn get_screen(
             let view_player =
                 button("Ver jogador").on_press(Message::ChangeScreen(Screen::ViewPlayer));
 
-            let mut left_bottom_row = row![settings, view_player, info]
+            let session = button("Sessão").on_press(Message::ChangeScreen(Screen::Session));
+
+            let mut left_bottom_row = row![settings, view_player, session, info]
                 .spacing(15)
                 .width(Length::Fill);
             let right_bottom_row = row![minimize, close].spacing(15);
@@ -159,7 +264,17 @@ pub fn get_screen(
 
             let bottom_row = row![left_bottom_row, right_bottom_row].spacing(20);
 
-            let main_column = column![bar, container].spacing(10).height(COLUMN_HEIGHT);
+            let mut main_column = column![bar].spacing(10).height(COLUMN_HEIGHT);
+            if let Some(warning) = &app.config_warning {
+                let warning_row = row![
+                    text(warning.clone()).color(Color::from_rgb8(255, 170, 0)),
+                    secondary_button("OK").on_press(Message::DismissConfigWarning)
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center);
+                main_column = main_column.push(warning_row);
+            }
+            main_column = main_column.push(container);
 
             column![main_column, bottom_row].padding(10).spacing(10)
         }
@@ -182,7 +297,20 @@ pub fn get_screen(
                 Some(app.stats_type.clone()),
                 Message::StatsSelect,
             );
-            let stats_row = row![text("Stats:"), stats_select].spacing(10);
+            let threat_button =
+                button("Índice de ameaça").on_press(Message::ChangeScreen(Screen::Threat));
+            let color_tiers_button =
+                button("Cores dos stats").on_press(Message::ChangeScreen(Screen::ColorTiers));
+            let columns_button =
+                button("Colunas extras").on_press(Message::ChangeScreen(Screen::Columns));
+            let stats_row = row![
+                text("Stats:"),
+                stats_select,
+                threat_button,
+                color_tiers_button,
+                columns_button
+            ]
+            .spacing(10);
 
             let go_back = button("Voltar").on_press(Message::ChangeScreen(Screen::Main));
 
@@ -203,7 +331,7 @@ pub fn get_screen(
                     .spacing(20)
             }
 
-            let never_minimize_toggler = toggler(app.never_minimize)
+            let never_minimize_toggler = toggler(app.config.never_minimize)
                 .on_toggle(Message::ChangeNeverMinimize)
                 .size(20);
 
@@ -214,22 +342,22 @@ pub fn get_screen(
 
             let seconds_to_minimize_text = text(format!(
                 "Mostrar o KC Overlay por {} segundos após carregar os jogadores",
-                app.seconds_to_minimize
+                app.config.seconds_to_minimize
             ));
             let seconds_to_minimize_slider = slider(
                 5.0..=120.,
-                app.seconds_to_minimize as f64,
+                app.config.seconds_to_minimize as f64,
                 Message::ChangeSecondsToMinimize,
             )
             .width(240);
             let seconds_to_minimize_column =
                 column![seconds_to_minimize_text, seconds_to_minimize_slider].spacing(5);
 
-            if !app.never_minimize {
+            if !app.config.never_minimize {
                 main_column = main_column.push(seconds_to_minimize_column)
             }
 
-            let auto_manage_players_toggler = toggler(app.auto_manage_players)
+            let auto_manage_players_toggler = toggler(app.config.auto_manage_players)
                 .on_toggle(Message::ChangeRemoveEliminatedPlayers)
                 .size(20);
             let auto_manage_players_text =
@@ -239,11 +367,11 @@ pub fn get_screen(
 
             let window_scale_slider = slider(
                 50.0..=125.,
-                app.window_scale * 100.,
+                app.config.window_scale * 100.,
                 Message::WindowScaleChanged,
             );
             let window_scale_row = row![
-                text(format!("Tamanho da janela ({}x):", app.window_scale)),
+                text(format!("Tamanho da janela ({}x):", app.config.window_scale)),
                 window_scale_slider
             ]
             .spacing(10);
@@ -251,7 +379,17 @@ pub fn get_screen(
             main_column = main_column.push(auto_manage_players_row);
             main_column = main_column.push(window_scale_row);
 
-            column![main_column, go_back].padding(10).spacing(10)
+            let snapshots_button =
+                button("Histórico de stats").on_press(Message::ChangeScreen(Screen::Snapshots));
+            let cheater_rules_button =
+                button("Regras de cheater").on_press(Message::ChangeScreen(Screen::CheaterRules));
+
+            column![
+                main_column,
+                row![go_back, snapshots_button, cheater_rules_button].spacing(15)
+            ]
+            .padding(10)
+            .spacing(10)
         }
         Screen::Welcome => {
             let welcome_text = text("Muito obrigado por usar a overlay! Selecione o client que você usa para proseguir.");
@@ -299,7 +437,12 @@ pub fn get_screen(
             let go_back = button("Voltar").on_press(Message::ChangeScreen(Screen::Main));
 
             let discord_column = column![thanks_text, discord_button].spacing(10).height(125);
-            let credits_column = column![creditos, github].spacing(10);
+            let config_path = text(format!(
+                "Configurações salvas em {}",
+                config::get_config_file_path()
+            ))
+            .size(12);
+            let credits_column = column![creditos, github, config_path].spacing(10);
 
             let main_column = column![discord_column, credits_column].height(COLUMN_HEIGHT);
 
@@ -319,7 +462,42 @@ pub fn get_screen(
 
             let go_back = button("Voltar").on_press(Message::ChangeScreen(Screen::Main));
 
-            if let Some(player) = &app.searched_player {
+            if let SearchedPlayer::Loading = app.searched_player {
+                main_column = main_column.push(text("Carregando jogador..."));
+            } else if let SearchedPlayer::Retrying(attempt) = app.searched_player {
+                main_column = main_column.push(text(format!(
+                    "Carregando jogador... tentando novamente ({}/{})",
+                    attempt, app.retry_policy.max_retries
+                )));
+            } else if let SearchedPlayer::Failed = app.searched_player {
+                main_column = main_column.push(
+                    text("Não foi possível consultar o jogador, tente novamente.")
+                        .color(Color::from_rgb8(237, 135, 150)),
+                );
+            } else if let SearchedPlayer::Found(player) = &app.searched_player {
+                let status_message = match &player.status {
+                    PlayerStatus::Found => None,
+                    PlayerStatus::Nicked => Some(
+                        text(format!("{} está usando um nick.", player.username))
+                            .color(Color::from_rgb8(255, 255, 0)),
+                    ),
+                    PlayerStatus::NotFound => Some(
+                        text(format!(
+                            "Jogador {} não encontrado. Verifique o nome digitado.",
+                            player.username
+                        ))
+                        .color(Color::from_rgb8(170, 170, 170)),
+                    ),
+                    PlayerStatus::Error(e) => Some(
+                        text(format!("A API do Mush retornou um erro: {e}"))
+                            .color(Color::from_rgb8(237, 135, 150)),
+                    ),
+                };
+                if let Some(status_message) = status_message {
+                    main_column = main_column.push(status_message);
+                    return column![main_column, go_back].spacing(10).padding(10);
+                }
+
                 let connected = match player.is_connected {
                     true => text("Sim").color(Color::from_rgb8(166, 218, 149)),
                     false => text("Não").color(Color::from_rgb8(237, 135, 150)),
@@ -338,28 +516,23 @@ pub fn get_screen(
                 );
                 let last_login_widget = text(last_login_date);
 
-                let clan = if let Some(value) = &player.clan {
-                    format!("[{}]", value)
+                let username_widget = minecraft_text(&player.name_text());
+                let clan_widget = minecraft_text(&player.clan_text());
+                let former_usernames = if player.former_usernames.is_empty() {
+                    text("")
                 } else {
-                    String::new()
+                    text(format!(
+                        "Antes conhecido como: {}",
+                        player.former_usernames.join(", ")
+                    ))
+                    .color(Color::from_rgb8(170, 170, 170))
                 };
 
-                let username_widget =
-                    text(player.username.clone()).color(player.username_color.to_color());
-                let clan_widget = text(clan).color(player.clan_color.to_color());
-
-                let player_column = match &player.stats {
-                    crate::stats::Stats::Bedwars(bedwars) => {
+                let player_column = match player.stats(&app.searched_player_stats_type) {
+                    Some(Stats::Bedwars(bedwars)) => {
                         let hours_played = text(format!("Horas jogadas: {}", bedwars.hours_played));
 
-                        let level_widget = row![
-                            text(format!("[{}", bedwars.level))
-                                .color(bedwars.level_color.to_color()),
-                            text(bedwars.level_symbol.clone())
-                                .font(Font::with_name("Noto Sans Symbols 2"))
-                                .color(bedwars.level_color.to_color()),
-                            text("]").color(bedwars.level_color.to_color())
-                        ];
+                        let level_widget = bedwars_level_badge(bedwars);
                         let (
                             winstreak_widget,
                             winrate_widget,
@@ -373,10 +546,34 @@ pub fn get_screen(
                             deaths,
                             assists,
                         ) = (
-                            text(format!("Winstreak: {}", bedwars.winstreak)),
-                            text(format!("WLR: {:.2}", bedwars.winrate)),
-                            text(format!("FKDR: {:.2}", bedwars.final_kill_death_ratio)),
-                            text(format!("KDR: {:.2}", bedwars.kill_death_ratio)),
+                            tiered_text(
+                                &app.config.color_tiers,
+                                "Winstreak",
+                                "winstreak",
+                                bedwars.winstreak as f64,
+                                0,
+                            ),
+                            tiered_text(
+                                &app.config.color_tiers,
+                                "WLR",
+                                "winrate",
+                                bedwars.winrate as f64,
+                                2,
+                            ),
+                            tiered_text(
+                                &app.config.color_tiers,
+                                "FKDR",
+                                "final_kill_death_ratio",
+                                bedwars.final_kill_death_ratio as f64,
+                                2,
+                            ),
+                            tiered_text(
+                                &app.config.color_tiers,
+                                "KDR",
+                                "kill_death_ratio",
+                                bedwars.kill_death_ratio as f64,
+                                2,
+                            ),
                             text(format!("Vitórias: {}", bedwars.wins)),
                             text(format!("Derrotas: {}", bedwars.losses)),
                             text(format!("Final kills: {}", bedwars.final_kills)),
@@ -385,6 +582,17 @@ pub fn get_screen(
                             text(format!("Mortes: {}", bedwars.deaths)),
                             text(format!("Assistências: {}", bedwars.assists)),
                         );
+                        let derived_column = column![
+                            text(format!("Partidas: {}", bedwars.games_played())),
+                            text(format!(
+                                "Final kills/partida: {:.2}",
+                                bedwars.final_kills_per_game()
+                            )),
+                            text(format!("Kills/partida: {:.2}", bedwars.kills_per_game())),
+                            text(format!("Vitórias/hora: {:.2}", bedwars.wins_per_hour())),
+                            text(format!("Assistências/kill: {:.2}", bedwars.assist_ratio())),
+                        ]
+                        .spacing(10);
 
                         let username_row =
                             row![level_widget, username_widget, clan_widget].spacing(5);
@@ -411,16 +619,822 @@ pub fn get_screen(
 
                         column![
                             username_row,
+                            former_usernames,
+                            row![left_column, middle_column, right_column, derived_column]
+                                .spacing(40)
+                        ]
+                        .spacing(10)
+                    }
+                    Some(Stats::Skywars(skywars)) => {
+                        let username_row =
+                            row![skywars_level_badge(skywars), username_widget, clan_widget]
+                                .spacing(5);
+
+                        let left_column =
+                            column![connected_row, first_login_widget, last_login_widget]
+                                .spacing(10);
+                        let middle_column = column![
+                            tiered_text(
+                                &app.config.color_tiers,
+                                "Winstreak",
+                                "winstreak",
+                                skywars.winstreak as f64,
+                                0,
+                            ),
+                            tiered_text(
+                                &app.config.color_tiers,
+                                "WLR",
+                                "winrate",
+                                skywars.winrate as f64,
+                                2,
+                            ),
+                            tiered_text(
+                                &app.config.color_tiers,
+                                "KDR",
+                                "kill_death_ratio",
+                                skywars.kill_death_ratio as f64,
+                                2,
+                            ),
+                        ]
+                        .spacing(10);
+                        let right_column = column![
+                            text(format!("Vitórias: {}", skywars.wins)),
+                            text(format!("Derrotas: {}", skywars.losses)),
+                            text(format!("Kills: {}", skywars.kills)),
+                            text(format!("Mortes: {}", skywars.deaths)),
+                            text(format!("Partidas: {}", skywars.wins + skywars.losses)),
+                        ]
+                        .spacing(10);
+
+                        column![
+                            username_row,
+                            former_usernames,
+                            row![left_column, middle_column, right_column].spacing(60)
+                        ]
+                        .spacing(10)
+                    }
+                    Some(Stats::Duels(duels)) => {
+                        let username_row = row![username_widget, clan_widget].spacing(5);
+
+                        let left_column =
+                            column![connected_row, first_login_widget, last_login_widget]
+                                .spacing(10);
+                        let middle_column = column![
+                            tiered_text(
+                                &app.config.color_tiers,
+                                "Winstreak",
+                                "winstreak",
+                                duels.winstreak as f64,
+                                0,
+                            ),
+                            tiered_text(
+                                &app.config.color_tiers,
+                                "Melhor winstreak",
+                                "winstreak",
+                                duels.best_winstreak as f64,
+                                0,
+                            ),
+                            tiered_text(
+                                &app.config.color_tiers,
+                                "WLR",
+                                "winrate",
+                                duels.winrate as f64,
+                                2,
+                            ),
+                        ]
+                        .spacing(10);
+                        let right_column = column![
+                            text(format!("Vitórias: {}", duels.wins)),
+                            text(format!("Derrotas: {}", duels.losses)),
+                            text(format!("Partidas: {}", duels.wins + duels.losses)),
+                        ]
+                        .spacing(10);
+
+                        column![
+                            username_row,
+                            former_usernames,
                             row![left_column, middle_column, right_column].spacing(60)
                         ]
                         .spacing(10)
                     }
+                    None => column![text("Sem stats para este modo.")],
                 };
 
-                main_column = main_column.push(player_column);
+                let mut details = column![player_column].spacing(15);
+                if player.is_possible_cheater() {
+                    details = details.push(
+                        text(format!(
+                            "Possível cheater: {}",
+                            player.cheater_flags.join("; ")
+                        ))
+                        .color(Color::from_rgb8(255, 85, 85))
+                        .size(12),
+                    );
+                }
+
+                // Campos do perfil que não têm lugar nos stats, e a resposta da API para achar
+                // campos que o overlay ainda não conhece.
+                let raw_json_button = secondary_button(if app.show_raw_json {
+                    "Esconder JSON"
+                } else {
+                    "Ver JSON da API"
+                })
+                .on_press(Message::ToggleRawJson);
+                details = details
+                    .push(profile_fields(&player.raw_json))
+                    .push(raw_json_button);
+                if app.show_raw_json {
+                    details = details.push(
+                        text(serde_json::to_string_pretty(&player.raw_json).unwrap_or_default())
+                            .size(11),
+                    );
+                }
+
+                main_column =
+                    main_column.push(scrollable(details).height(Length::Fill).width(Length::Fill));
             }
 
-            column![main_column, go_back].spacing(10).padding(10)
+            let mut bottom_row = row![go_back].spacing(15);
+            if let SearchedPlayer::Found(player) = &app.searched_player {
+                if !player.encounters.is_empty() {
+                    bottom_row = bottom_row.push(
+                        button(text(format!("Encontros ({})", player.encounters.len())))
+                            .on_press(Message::ChangeScreen(Screen::Encounters)),
+                    );
+                }
+                if app
+                    .searched_player_history
+                    .contains_key(app.searched_player_stats_type.id())
+                {
+                    bottom_row = bottom_row.push(
+                        button("Histórico").on_press(Message::ChangeScreen(Screen::PlayerHistory)),
+                    );
+                }
+            }
+
+            column![main_column, bottom_row].spacing(10).padding(10)
+        }
+        Screen::Threat => {
+            let settings = app.config.threat;
+
+            let formula_text = text(
+                "Índice = nível^a × FKDR^2b × (1 + WLR)^c × (1 + WS/10)^d. Ajuste os pesos abaixo (0 ignora o stat).",
+            );
+
+            let weights = [
+                ("Nível (a)", ThreatWeight::Level, settings.level_weight),
+                ("FKDR (b)", ThreatWeight::Fkdr, settings.fkdr_weight),
+                ("WLR (c)", ThreatWeight::Wlr, settings.wlr_weight),
+                (
+                    "Winstreak (d)",
+                    ThreatWeight::Winstreak,
+                    settings.winstreak_weight,
+                ),
+            ];
+            let mut main_column = column![formula_text].spacing(20).height(COLUMN_HEIGHT);
+            for (name, weight, value) in weights {
+                let weight_slider = slider(0.0..=2., value, move |value| {
+                    Message::ThreatWeightChanged(weight, value)
+                })
+                .step(0.1)
+                .width(240);
+                main_column = main_column.push(
+                    row![
+                        text(format!("{name}: {value:.1}")).width(150),
+                        weight_slider
+                    ]
+                    .spacing(10),
+                );
+            }
+
+            let sort_toggler = toggler(settings.sort_by_threat)
+                .on_toggle(Message::SortByThreatChanged)
+                .size(20);
+            let sort_row = row![
+                sort_toggler,
+                text("Ordenar jogadores pelo índice em vez do nível")
+            ]
+            .spacing(10);
+
+            let alert_text = if settings.alert_threshold > 0. {
+                format!(
+                    "Alertar jogadores com índice acima de {:.0}",
+                    settings.alert_threshold
+                )
+            } else {
+                String::from("Alerta desativado")
+            };
+            let alert_slider = slider(
+                0.0..=20000.,
+                settings.alert_threshold,
+                Message::ThreatAlertThresholdChanged,
+            )
+            .step(500.)
+            .width(240);
+
+            main_column = main_column
+                .push(sort_row)
+                .push(column![text(alert_text), alert_slider].spacing(5));
+
+            let go_back = button("Voltar").on_press(Message::ChangeScreen(Screen::Settings));
+
+            column![main_column, go_back].padding(10).spacing(10)
+        }
+        Screen::ColorTiers => {
+            let explanation = text("Cada stat tem 5 limites: abaixo do primeiro o valor fica cinza, depois branco, amarelo, laranja, vermelho e, acima do último, roxo.");
+
+            // Cor que o valor recebe ao atingir cada limite.
+            let colors = color_tiers::tier_colors();
+            let color_names = ["Branco", "Amarelo", "Laranja", "Vermelho", "Roxo"];
+            let mut color_row = row![text("").width(100)].spacing(5);
+            for (color, name) in colors.iter().skip(1).zip(color_names) {
+                color_row = color_row.push(text(name).color(color.to_color()).width(70));
+            }
+
+            let mut main_column = column![explanation, color_row]
+                .spacing(15)
+                .height(COLUMN_HEIGHT);
+            let mut has_invalid_input = false;
+            for stat_tiers in &app.config.color_tiers.stats {
+                let mut tiers_row = row![text(stat_tiers.name.clone()).width(100)].spacing(5);
+                for (index, threshold) in stat_tiers.thresholds.iter().enumerate() {
+                    let input = app.color_tier_inputs.get(&(stat_tiers.stat.clone(), index));
+                    // O campo só difere do limite salvo quando o valor digitado foi recusado.
+                    let invalid = input.is_some_and(|input| {
+                        input.replace(',', ".").parse::<f64>().ok() != Some(*threshold)
+                    });
+                    has_invalid_input |= invalid;
+
+                    let value = input.cloned().unwrap_or_else(|| threshold.to_string());
+                    let input = match invalid {
+                        true => invalid_text_input("0", &value),
+                        false => text_input("0", &value),
+                    };
+                    let stat = stat_tiers.stat.clone();
+                    let input = input
+                        .on_input(move |input| {
+                            Message::ColorTierChanged(stat.clone(), index, input)
+                        })
+                        .width(70);
+                    tiers_row = tiers_row.push(input);
+                }
+                main_column = main_column.push(tiers_row);
+            }
+            if has_invalid_input {
+                main_column = main_column.push(
+                    text("Os limites precisam ser números em ordem crescente.")
+                        .color(Color::from_rgb8(237, 135, 150)),
+                );
+            }
+
+            let reset = secondary_button("Restaurar padrão").on_press(Message::ResetColorTiers);
+            main_column = main_column.push(reset);
+
+            let go_back = button("Voltar").on_press(Message::ChangeScreen(Screen::Settings));
+
+            column![main_column, go_back].padding(10).spacing(10)
+        }
+        Screen::Columns => {
+            let explanation = text("Stats derivados que podem ser mostrados como colunas extras na tela principal. Cada coluna só aparece nos modos que têm o stat.");
+
+            let deltas_toggler = toggler(app.config.show_deltas)
+                .on_toggle(Message::ShowDeltasChanged)
+                .size(20);
+            let deltas_row = row![
+                deltas_toggler,
+                text("Mostrar a mudança dos stats desde a última vez que o jogador foi visto")
+            ]
+            .spacing(10);
+
+            let mut main_column = column![explanation, deltas_row]
+                .spacing(20)
+                .height(COLUMN_HEIGHT);
+            for stats::DerivedColumn { name, column } in stats::derived_columns() {
+                let enabled = app.config.extra_columns.contains(&column.stat);
+                let stat = column.stat.clone();
+                let column_toggler = toggler(enabled)
+                    .on_toggle(move |enabled| Message::ExtraColumnToggled(stat.clone(), enabled))
+                    .size(20);
+                main_column = main_column.push(
+                    row![column_toggler, text(format!("{name} ({})", column.header))].spacing(10),
+                );
+            }
+
+            let go_back = button("Voltar").on_press(Message::ChangeScreen(Screen::Settings));
+
+            column![main_column, go_back].padding(10).spacing(10)
+        }
+        Screen::Snapshots => {
+            let explanation = text("Os stats de cada jogador consultado são salvos localmente para ver como ele mudou com o tempo.");
+
+            let retention = app.config.snapshot_retention;
+            let days_text = if retention.days > 0 {
+                format!("Apagar snapshots com mais de {} dias", retention.days)
+            } else {
+                String::from("Nunca apagar snapshots antigos")
+            };
+            let days_slider = slider(
+                0.0..=365.,
+                retention.days as f64,
+                Message::SnapshotRetentionDaysChanged,
+            )
+            .step(5.)
+            .width(240);
+
+            let max_text = if retention.max_per_mode > 0 {
+                format!(
+                    "Manter até {} snapshots por jogador em cada modo",
+                    retention.max_per_mode
+                )
+            } else {
+                String::from("Sem limite de snapshots por jogador")
+            };
+            let max_slider = slider(
+                0.0..=500.,
+                retention.max_per_mode as f64,
+                Message::SnapshotRetentionMaxChanged,
+            )
+            .step(10.)
+            .width(240);
+
+            let main_column = column![
+                explanation,
+                column![text(days_text), days_slider].spacing(5),
+                column![text(max_text), max_slider].spacing(5),
+                text(format!(
+                    "Arquivo: {}",
+                    crate::snapshots::get_snapshots_db_path()
+                ))
+                .color(Color::from_rgb8(170, 170, 170))
+                .size(12)
+            ]
+            .spacing(20)
+            .height(COLUMN_HEIGHT);
+
+            let go_back = button("Voltar").on_press(Message::ChangeScreen(Screen::Settings));
+
+            column![main_column, go_back].padding(10).spacing(10)
+        }
+        Screen::PlayerHistory => {
+            let stats_type = &app.searched_player_stats_type;
+            let username = match &app.searched_player {
+                SearchedPlayer::Found(player) => player.username.clone(),
+                _ => String::new(),
+            };
+            let title = text(format!("Histórico de {username} ({stats_type})"));
+
+            let columns = stats_type.columns();
+            let mut date_column = Column::new().push(text("Data")).width(200);
+            let mut stat_columns: Vec<Column<'static, Message, theme::Theme, Renderer>> = columns
+                .iter()
+                .map(|column| {
+                    Column::new()
+                        .align_x(Alignment::Center)
+                        .push(text(column.header.clone()))
+                })
+                .collect();
+
+            // Só os mais recentes cabem na tela.
+            let snapshots = app
+                .searched_player_history
+                .get(stats_type.id())
+                .map(|x| x.as_slice())
+                .unwrap_or_default();
+            for snapshot in snapshots.iter().take(10) {
+                let mut date_row =
+                    row![text(util::unix_time_to_date(snapshot.taken_at))].spacing(5);
+                if snapshot.username != username {
+                    date_row = date_row.push(
+                        text(format!("({})", snapshot.username))
+                            .color(Color::from_rgb8(170, 170, 170))
+                            .size(12),
+                    );
+                }
+                date_column = date_column.push(date_row);
+                stat_columns = push_row(
+                    stat_columns,
+                    columns
+                        .iter()
+                        .map(|column| {
+                            let value = match snapshot.values.get(&column.stat) {
+                                Some(value) => format!("{:.*}", column.decimals, value),
+                                None => String::from("-"),
+                            };
+                            text(value).into()
+                        })
+                        .collect(),
+                );
+            }
+
+            let mut table = row![date_column].spacing(15);
+            for stat_column in stat_columns {
+                table = table.push(stat_column);
+            }
+
+            let main_column = column![title, table].spacing(20).height(COLUMN_HEIGHT);
+
+            let go_back = button("Voltar").on_press(Message::ChangeScreen(Screen::ViewPlayer));
+
+            column![main_column, go_back].padding(10).spacing(10)
+        }
+        Screen::Session => {
+            let username_input = text_input("Seu nome no Mush", &app.own_username_input)
+                .on_input(Message::OwnUsernameInputChanged)
+                .on_submit(Message::SetOwnUsername)
+                .width(240);
+            let username_row = row![
+                username_input,
+                button("Salvar").on_press(Message::SetOwnUsername)
+            ]
+            .spacing(10);
+
+            let mut main_column = column![username_row].spacing(20).height(COLUMN_HEIGHT);
+
+            let go_back = button("Voltar").on_press(Message::ChangeScreen(Screen::Main));
+
+            if app.config.username.is_empty() {
+                main_column = main_column.push(text(
+                    "Digite seu nome para acompanhar os stats ganhos durante a sessão.",
+                ));
+                return column![main_column, go_back].spacing(10).padding(10);
+            }
+            if app.session.last.is_none() {
+                main_column = main_column.push(text("Carregando seus stats..."));
+                return column![main_column, go_back].spacing(10).padding(10);
+            }
+
+            let session = &app.session;
+            let stats_type = &app.stats_type;
+            let gained = |stat: &str| {
+                session
+                    .gained(stats_type, stat)
+                    .map(|x| format!("{:+.0}", x))
+                    .unwrap_or(String::from("-"))
+            };
+            let change = |stat: &str, decimals: usize| {
+                session
+                    .stat(stats_type, stat)
+                    .map(|(first, last)| format!("{:.*} → {:.*}", decimals, first, decimals, last))
+                    .unwrap_or(String::from("-"))
+            };
+            let session_fkdr = session
+                .session_fkdr(stats_type)
+                .map(|x| format!("{:.2}", x))
+                .unwrap_or(String::from("-"));
+
+            let title = text(format!(
+                "Sessão de {} desde {} ({})",
+                app.config.username,
+                util::unix_time_to_date(session.started_at),
+                stats_type
+            ));
+            let gained_column = column![
+                text(format!("Vitórias: {}", gained("wins"))),
+                text(format!("Derrotas: {}", gained("losses"))),
+                text(format!("Final kills: {}", gained("final_kills"))),
+                text(format!("Final deaths: {}", gained("final_deaths"))),
+            ]
+            .spacing(10);
+            let ratio_column = column![
+                text(format!("FKDR da sessão: {session_fkdr}")),
+                text(format!("FKDR: {}", change("final_kill_death_ratio", 2))),
+                text(format!("WLR: {}", change("winrate", 2))),
+                text(format!("Winstreak: {}", change("winstreak", 0))),
+            ]
+            .spacing(10);
+
+            // Mudanças de cada atualização, normalmente uma partida por vez.
+            let mut matches_column = column![text("Partidas:")].spacing(5);
+            let matches = session.matches(stats_type);
+            if matches.is_empty() {
+                matches_column = matches_column.push(
+                    text("Nenhuma partida registrada ainda.")
+                        .color(Color::from_rgb8(170, 170, 170)),
+                );
+            }
+            for match_change in matches.into_iter().take(5) {
+                let names = [
+                    ("wins", "vitórias"),
+                    ("losses", "derrotas"),
+                    ("final_kills", "final kills"),
+                    ("final_deaths", "final deaths"),
+                    ("kills", "kills"),
+                    ("deaths", "mortes"),
+                ];
+                let changes: Vec<String> = names
+                    .iter()
+                    .filter_map(|(stat, name)| {
+                        Some(format!("{:+.0} {name}", match_change.deltas.get(*stat)?))
+                    })
+                    .collect();
+                matches_column = matches_column.push(
+                    text(format!(
+                        "{}: {}",
+                        util::unix_time_to_date(match_change.at),
+                        changes.join(", ")
+                    ))
+                    .size(12),
+                );
+            }
+
+            main_column = main_column
+                .push(title)
+                .push(row![gained_column, ratio_column].spacing(60))
+                .push(matches_column);
+
+            let reset = secondary_button("Reiniciar sessão").on_press(Message::ResetSession);
+
+            column![main_column, row![go_back, reset].spacing(15)]
+                .spacing(10)
+                .padding(10)
+        }
+        Screen::Encounters => {
+            let (username, encounters) = match &app.searched_player {
+                SearchedPlayer::Found(player) => {
+                    (player.username.clone(), player.encounters.clone())
+                }
+                _ => (String::new(), vec![]),
+            };
+            let title = text(format!(
+                "Encontros com {username}: {}",
+                encounter_summary(&encounters)
+            ));
+
+            let mut date_column = column![text("Data")].width(170);
+            let mut mode_column = column![text("Modo")].width(150);
+            let mut username_column = column![text("Nome")].width(140);
+            let mut team_column = column![text("Time")].width(100);
+            let mut result_column = column![text("Resultado")];
+            // Só os mais recentes cabem na tela.
+            for encounter in encounters.iter().take(10) {
+                date_column = date_column.push(text(util::unix_time_to_date(encounter.at)));
+                mode_column =
+                    mode_column.push(text(StatsType::from_string(&encounter.mode).to_string()));
+                username_column = username_column.push(text(encounter.username.clone()));
+                team_column = team_column.push(text(match encounter.same_team {
+                    Some(true) => "Mesmo time",
+                    Some(false) => "Adversário",
+                    None => "-",
+                }));
+                result_column = result_column.push(match encounter.won {
+                    Some(true) => text("Vitória").color(Color::from_rgb8(166, 218, 149)),
+                    Some(false) => text("Derrota").color(Color::from_rgb8(237, 135, 150)),
+                    None => text("-"),
+                });
+            }
+
+            let table = row![
+                date_column,
+                mode_column,
+                username_column,
+                team_column,
+                result_column
+            ]
+            .spacing(15);
+            let main_column = column![title, table].spacing(20).height(COLUMN_HEIGHT);
+
+            let go_back = button("Voltar").on_press(Message::ChangeScreen(Screen::ViewPlayer));
+
+            column![main_column, go_back].padding(10).spacing(10)
+        }
+        Screen::CheaterRules => {
+            let rules = app.config.cheater_rules;
+            let explanation = text("Jogadores que disparam alguma regra são marcados como [CHEATER?]. Passe o mouse na marca para ver o motivo.");
+
+            let rule_toggler = |rule: CheaterRule, enabled: bool, description: &str| {
+                row![
+                    toggler(enabled)
+                        .on_toggle(move |enabled| Message::CheaterRuleToggled(rule, enabled))
+                        .size(20),
+                    text(description.to_string())
+                ]
+                .spacing(10)
+            };
+            let parameter_slider = |parameter: RuleParameter,
+                                    range: std::ops::RangeInclusive<f64>,
+                                    value: f64,
+                                    step: f64| {
+                slider(range, value, move |value| {
+                    Message::CheaterRuleParameterChanged(parameter, value)
+                })
+                .step(step)
+                .width(200)
+            };
+
+            let mut main_column = column![explanation].spacing(15).height(COLUMN_HEIGHT);
+
+            main_column = main_column.push(rule_toggler(
+                CheaterRule::NewAccount,
+                rules.new_account,
+                &format!(
+                    "Conta com menos de {:.0} dias e FKDR acima de {:.1}",
+                    rules.new_account_days, rules.new_account_fkdr
+                ),
+            ));
+            if rules.new_account {
+                main_column = main_column.push(
+                    row![
+                        parameter_slider(
+                            RuleParameter::NewAccountDays,
+                            1.0..=60.,
+                            rules.new_account_days,
+                            1.
+                        ),
+                        parameter_slider(
+                            RuleParameter::NewAccountFkdr,
+                            1.0..=20.,
+                            rules.new_account_fkdr,
+                            0.5
+                        )
+                    ]
+                    .spacing(20),
+                );
+            }
+
+            main_column = main_column.push(rule_toggler(
+                CheaterRule::Winstreak,
+                rules.winstreak,
+                &format!(
+                    "Winstreak de {:.0} ou mais com até {:.0} horas jogadas",
+                    rules.winstreak_min, rules.winstreak_max_hours
+                ),
+            ));
+            if rules.winstreak {
+                main_column = main_column.push(
+                    row![
+                        parameter_slider(
+                            RuleParameter::WinstreakMin,
+                            5.0..=200.,
+                            rules.winstreak_min,
+                            5.
+                        ),
+                        parameter_slider(
+                            RuleParameter::WinstreakMaxHours,
+                            1.0..=100.,
+                            rules.winstreak_max_hours,
+                            1.
+                        )
+                    ]
+                    .spacing(20),
+                );
+            }
+
+            main_column = main_column.push(rule_toggler(
+                CheaterRule::StatJump,
+                rules.stat_jump,
+                &format!(
+                    "FKDR subiu {:.1} ou mais desde o último snapshot",
+                    rules.stat_jump_fkdr
+                ),
+            ));
+            if rules.stat_jump {
+                main_column = main_column.push(parameter_slider(
+                    RuleParameter::StatJumpFkdr,
+                    0.5..=10.,
+                    rules.stat_jump_fkdr,
+                    0.5,
+                ));
+            }
+
+            let go_back = button("Voltar").on_press(Message::ChangeScreen(Screen::Settings));
+
+            column![main_column, go_back].padding(10).spacing(10)
         }
     }
 }
+
+// Campos simples da resposta da API, em duas colunas. Os objetos aparecem como "objeto.campo",
+// menos os stats, que já aparecem acima.
+fn profile_fields(raw_json: &Value) -> Element<'static, Message, theme::Theme, Renderer> {
+    let mut fields: Vec<(String, String)> = vec![];
+    if let Some(object) = raw_json.as_object() {
+        for (key, value) in object {
+            match value {
+                Value::Object(inner) if key != "stats" => {
+                    for (inner_key, inner_value) in inner {
+                        if let Some(value) = field_to_string(inner_value) {
+                            fields.push((format!("{key}.{inner_key}"), value));
+                        }
+                    }
+                }
+                _ => {
+                    if let Some(value) = field_to_string(value) {
+                        fields.push((key.clone(), value));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut keys_column = Column::new().spacing(3);
+    let mut values_column = Column::new().spacing(3);
+    for (key, value) in fields {
+        keys_column = keys_column.push(text(key).size(12).color(Color::from_rgb8(170, 170, 170)));
+        values_column = values_column.push(text(value).size(12));
+    }
+    row![keys_column, values_column].spacing(20).into()
+}
+
+// Valor de um campo do JSON. Listas, como os nomes antigos e as insígnias, viram os itens
+// separados por vírgula. Objetos retornam None.
+fn field_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(string) => Some(string.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(boolean) => Some(if *boolean { "Sim" } else { "Não" }.to_string()),
+        Value::Null => Some(String::from("-")),
+        Value::Array(items) if items.is_empty() => Some(String::from("-")),
+        Value::Array(items) => Some(
+            items
+                .iter()
+                .map(|item| field_to_string(item).unwrap_or_else(|| item.to_string()))
+                .collect::<Vec<String>>()
+                .join(", "),
+        ),
+        Value::Object(_) => None,
+    }
+}
+
+// Marca de possível cheater, com as regras que dispararam ao passar o mouse.
+fn cheater_badge(flags: &[String]) -> Element<'static, Message, theme::Theme, Renderer> {
+    let rules = flags.iter().fold(Column::new().spacing(3), |column, flag| {
+        column.push(text(format!("• {flag}")).size(12))
+    });
+    tooltip(
+        text("[CHEATER?]")
+            .color(Color::from_rgb8(255, 0, 0))
+            .size(12),
+        rules,
+    )
+    .into()
+}
+
+// Resumo dos encontros, ex: "5 encontros, você venceu 3".
+fn encounter_summary(encounters: &[Encounter]) -> String {
+    let wins = encounters.iter().filter(|x| x.won == Some(true)).count();
+    let plural = if encounters.len() == 1 { "" } else { "s" };
+    if wins > 0 {
+        format!("{} encontro{plural}, você venceu {wins}", encounters.len())
+    } else {
+        format!("{} encontro{plural}", encounters.len())
+    }
+}
+
+// Mudança de um stat, ex: +0.25 em verde. Mudanças que somem com o arredondamento não aparecem.
+fn delta_text(delta: f64, decimals: usize) -> Option<Text<'static, theme::Theme, Renderer>> {
+    let formatted = format!("{:+.*}", decimals, delta);
+    if formatted
+        .trim_start_matches(['+', '-'])
+        .trim_matches(['0', '.'])
+        .is_empty()
+    {
+        return None;
+    }
+    let color = if delta > 0. {
+        Color::from_rgb8(166, 218, 149)
+    } else {
+        Color::from_rgb8(237, 135, 150)
+    };
+    Some(text(formatted).color(color).size(11))
+}
+
+// Texto "Nome: valor" colorido de acordo com as faixas configuradas para o stat.
+fn tiered_text(
+    color_tiers: &ColorTiers,
+    label: &str,
+    stat: &str,
+    value: f64,
+    decimals: usize,
+) -> Text<'static, theme::Theme, Renderer> {
+    text(format!("{label}: {:.*}", decimals, value))
+        .color(color_tiers.color(stat, value).to_color())
+}
+
+// Nível do jogador no formato do jogo, ex: [120✫].
+fn level_badge(stats: &Stats) -> Row<'static, Message, theme::Theme, Renderer> {
+    match stats {
+        Stats::Bedwars(bedwars) => bedwars_level_badge(bedwars),
+        Stats::Skywars(skywars) => skywars_level_badge(skywars),
+        Stats::Duels(_) => row![],
+    }
+}
+
+fn bedwars_level_badge(bedwars: &Bedwars) -> Row<'static, Message, theme::Theme, Renderer> {
+    minecraft_text(&bedwars.badge)
+}
+
+fn skywars_level_badge(skywars: &Skywars) -> Row<'static, Message, theme::Theme, Renderer> {
+    row![text(format!("[{}]", skywars.level)).color(Color::from_rgb8(255, 170, 0))]
+}
+
+// Adiciona uma linha de valores às colunas de stats da tela principal.
+fn push_row(
+    columns: Vec<Column<'static, Message, theme::Theme, Renderer>>,
+    values: Vec<Element<'static, Message, theme::Theme, Renderer>>,
+) -> Vec<Column<'static, Message, theme::Theme, Renderer>> {
+    columns
+        .into_iter()
+        .zip(values)
+        .map(|(column, value)| column.push(value))
+        .collect()
+}
@@ -0,0 +1,105 @@
+// Sessão do próprio jogador: os stats são consultados ao abrir o overlay e periodicamente, e a
+// diferença mostra o que foi ganho desde o início da sessão.
+
+use std::collections::BTreeMap;
+
+use crate::{player::Player, stats::StatsType};
+
+// Stats comparados entre uma consulta e outra.
+const TRACKED_STATS: [&str; 6] = [
+    "wins",
+    "losses",
+    "final_kills",
+    "final_deaths",
+    "kills",
+    "deaths",
+];
+
+// Mudança nos stats de um modo entre duas consultas, normalmente uma partida.
+#[derive(Debug, Clone)]
+pub struct MatchChange {
+    pub at: i64,
+    pub stats_type: StatsType,
+    pub deltas: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    pub started_at: i64,
+    pub first: Option<Player>,
+    pub last: Option<Player>,
+    pub matches: Vec<MatchChange>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Session {
+            started_at: chrono::Utc::now().timestamp_millis(),
+            ..Default::default()
+        }
+    }
+
+    // Registra uma nova consulta dos stats do jogador.
+    pub fn update(&mut self, player: Player) {
+        let now = chrono::Utc::now().timestamp_millis();
+        if self.first.is_none() {
+            self.first = Some(player.clone());
+        }
+
+        if let Some(last) = &self.last {
+            for (stats_type, stats) in &player.stats {
+                let Some(previous) = last.stats(stats_type) else {
+                    continue;
+                };
+                let deltas: BTreeMap<String, f64> = TRACKED_STATS
+                    .iter()
+                    .filter_map(|stat| {
+                        let delta = stats.value(stat)? - previous.value(stat)?;
+                        (delta != 0.).then(|| (stat.to_string(), delta))
+                    })
+                    .collect();
+                if !deltas.is_empty() {
+                    self.matches.push(MatchChange {
+                        at: now,
+                        stats_type: stats_type.clone(),
+                        deltas,
+                    });
+                }
+            }
+        }
+
+        self.last = Some(player);
+    }
+
+    // Valor do stat no início da sessão e agora.
+    pub fn stat(&self, stats_type: &StatsType, stat: &str) -> Option<(f64, f64)> {
+        let first = self.first.as_ref()?.stats(stats_type)?.value(stat)?;
+        let last = self.last.as_ref()?.stats(stats_type)?.value(stat)?;
+        Some((first, last))
+    }
+
+    pub fn gained(&self, stats_type: &StatsType, stat: &str) -> Option<f64> {
+        self.stat(stats_type, stat)
+            .map(|(first, last)| last - first)
+    }
+
+    // FKDR só das partidas da sessão.
+    pub fn session_fkdr(&self, stats_type: &StatsType) -> Option<f64> {
+        let final_kills = self.gained(stats_type, "final_kills")?;
+        let final_deaths = self.gained(stats_type, "final_deaths")?;
+        Some(if final_deaths == 0. {
+            final_kills
+        } else {
+            final_kills / final_deaths
+        })
+    }
+
+    // Mudanças de um modo, da mais recente para a mais antiga.
+    pub fn matches(&self, stats_type: &StatsType) -> Vec<&MatchChange> {
+        self.matches
+            .iter()
+            .rev()
+            .filter(|x| x.stats_type == *stats_type)
+            .collect()
+    }
+}
@@ -0,0 +1,199 @@
+// Histórico de stats: cada vez que um jogador é consultado, os stats de cada modo são salvos
+// com a data em um banco SQLite local.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use rusqlite::{params, Connection, Row};
+use serde::{Deserialize, Serialize};
+
+use crate::{encounters, paths, player::Player};
+
+pub fn get_snapshots_db_path() -> String {
+    format!("{}/kc_overlay_stats.db", paths::data_dir())
+}
+
+// Quanto tempo e quantos snapshots manter. 0 não limita.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnapshotRetention {
+    pub days: u64,
+    pub max_per_mode: u64,
+}
+
+impl Default for SnapshotRetention {
+    fn default() -> Self {
+        SnapshotRetention {
+            days: 90,
+            max_per_mode: 50,
+        }
+    }
+}
+
+// Stats de um modo em um momento.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub taken_at: i64,
+    pub username: String,
+    pub values: HashMap<String, f64>,
+}
+
+// Usa a conexão com o banco, abrindo na primeira vez. A conexão é compartilhada com os
+// encontros e só deve ser usada dentro de `util::blocking`.
+pub fn with_connection<T>(
+    work: impl FnOnce(&mut Connection) -> rusqlite::Result<T>,
+) -> rusqlite::Result<T> {
+    static CONNECTION: Mutex<Option<Connection>> = Mutex::new(None);
+
+    let mut connection = CONNECTION.lock().unwrap();
+    if connection.is_none() {
+        *connection = Some(open()?);
+    }
+    work(connection.as_mut().unwrap())
+}
+
+fn open() -> rusqlite::Result<Connection> {
+    let connection = Connection::open(get_snapshots_db_path())?;
+    connection.execute_batch(
+        "CREATE TABLE IF NOT EXISTS snapshots (
+            id INTEGER PRIMARY KEY,
+            uuid TEXT NOT NULL,
+            username TEXT NOT NULL,
+            mode TEXT NOT NULL,
+            taken_at INTEGER NOT NULL,
+            stats TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS snapshots_player ON snapshots (uuid, mode, taken_at);",
+    )?;
+    encounters::create_table(&connection)?;
+    Ok(connection)
+}
+
+// Salva os stats de todos os modos do jogador. Modos cujos stats não mudaram desde o último
+// snapshot são ignorados, assim entrar várias vezes na sala com alguém não enche o banco.
+pub fn save(player: &Player, retention: SnapshotRetention) {
+    if let Err(e) = try_save(player, retention) {
+        println!("Failed to save stats snapshot: {e}");
+    }
+}
+
+fn try_save(player: &Player, retention: SnapshotRetention) -> rusqlite::Result<()> {
+    let Some(uuid) = &player.uuid else {
+        return Ok(());
+    };
+    if player.stats.is_empty() {
+        return Ok(());
+    }
+
+    with_connection(|connection| {
+        let transaction = connection.transaction()?;
+        let now = chrono::Utc::now().timestamp_millis();
+
+        for (stats_type, stats) in &player.stats {
+            let values = serde_json::to_string(&stats.values()).unwrap();
+            let last_values: Option<String> = transaction
+                .query_row(
+                    "SELECT stats FROM snapshots WHERE uuid = ?1 AND mode = ?2
+                    ORDER BY taken_at DESC LIMIT 1",
+                    params![uuid, stats_type.id()],
+                    |row| row.get(0),
+                )
+                .ok();
+            if last_values.as_deref() == Some(values.as_str()) {
+                continue;
+            }
+
+            transaction.execute(
+                "INSERT INTO snapshots (uuid, username, mode, taken_at, stats)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![uuid, player.username, stats_type.id(), now, values],
+            )?;
+
+            if retention.max_per_mode > 0 {
+                transaction.execute(
+                    "DELETE FROM snapshots WHERE uuid = ?1 AND mode = ?2 AND id NOT IN (
+                        SELECT id FROM snapshots WHERE uuid = ?1 AND mode = ?2
+                        ORDER BY taken_at DESC LIMIT ?3
+                    )",
+                    params![uuid, stats_type.id(), retention.max_per_mode as i64],
+                )?;
+            }
+        }
+
+        transaction.commit()
+    })
+}
+
+// Remove snapshots mais antigos que o limite de dias. Executado ao abrir o overlay.
+pub fn prune(retention: SnapshotRetention) {
+    if retention.days == 0 {
+        return;
+    }
+    let oldest = chrono::Utc::now().timestamp_millis() - retention.days as i64 * 86_400_000;
+    let result = with_connection(|connection| {
+        connection.execute("DELETE FROM snapshots WHERE taken_at < ?1", params![oldest])
+    });
+    if let Err(e) = result {
+        println!("Failed to prune stats snapshots: {e}");
+    }
+}
+
+// Snapshot mais recente de cada modo.
+pub fn latest(uuid: &str) -> HashMap<String, Snapshot> {
+    let result = with_connection(|connection| {
+        let mut modes =
+            connection.prepare("SELECT DISTINCT mode FROM snapshots WHERE uuid = ?1")?;
+        let mut latest = connection.prepare(
+            "SELECT mode, taken_at, username, stats FROM snapshots WHERE uuid = ?1 AND mode = ?2
+            ORDER BY taken_at DESC LIMIT 1",
+        )?;
+
+        let modes = modes
+            .query_map(params![uuid], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        modes
+            .iter()
+            .map(|mode| latest.query_row(params![uuid, mode], snapshot_from_row))
+            .collect()
+    });
+
+    result.unwrap_or_else(|e| {
+        println!("Failed to read stats snapshots: {e}");
+        HashMap::new()
+    })
+}
+
+// Snapshots de um jogador em todos os modos, do mais recente para o mais antigo.
+pub fn history(uuid: &str) -> HashMap<String, Vec<Snapshot>> {
+    let result = with_connection(|connection| {
+        let mut statement = connection.prepare(
+            "SELECT mode, taken_at, username, stats FROM snapshots WHERE uuid = ?1
+            ORDER BY taken_at DESC",
+        )?;
+        let rows = statement.query_map(params![uuid], snapshot_from_row)?;
+
+        let mut history: HashMap<String, Vec<Snapshot>> = HashMap::new();
+        for row in rows {
+            let (mode, snapshot) = row?;
+            history.entry(mode).or_default().push(snapshot);
+        }
+        Ok(history)
+    });
+
+    result.unwrap_or_else(|e| {
+        println!("Failed to read stats snapshots: {e}");
+        HashMap::new()
+    })
+}
+
+// Linha com mode, taken_at, username e stats, nessa ordem.
+fn snapshot_from_row(row: &Row) -> rusqlite::Result<(String, Snapshot)> {
+    let stats: String = row.get(3)?;
+    Ok((
+        row.get(0)?,
+        Snapshot {
+            taken_at: row.get(1)?,
+            username: row.get(2)?,
+            values: serde_json::from_str(&stats).unwrap_or_default(),
+        },
+    ))
+}
@@ -1,21 +1,127 @@
-use std::fmt::Display;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    fs,
+    path::Path,
+    sync::OnceLock,
+};
 
-use crate::util::Rgb;
+use serde_json::Value;
+
+use crate::{config, minecraft_text::MinecraftText, util::Rgb};
 
 #[derive(Debug, Clone)]
 pub enum Stats {
     Bedwars(Bedwars),
+    Skywars(Skywars),
+    Duels(Duels),
+}
+
+impl Stats {
+    pub fn level(&self) -> i32 {
+        match self {
+            Stats::Bedwars(bedwars) => bedwars.level,
+            Stats::Skywars(skywars) => skywars.level,
+            // Duels não tem nível.
+            Stats::Duels(_) => 0,
+        }
+    }
+
+    // Valor de um stat pelo nome usado na tabela de modos.
+    pub fn value(&self, stat: &str) -> Option<f64> {
+        let value = match self {
+            Stats::Bedwars(bedwars) => match stat {
+                "level" => bedwars.level as f64,
+                "winstreak" => bedwars.winstreak as f64,
+                "winrate" => bedwars.winrate as f64,
+                "final_kill_death_ratio" => bedwars.final_kill_death_ratio as f64,
+                "kill_death_ratio" => bedwars.kill_death_ratio as f64,
+                "wins" => bedwars.wins as f64,
+                "losses" => bedwars.losses as f64,
+                "kills" => bedwars.kills as f64,
+                "deaths" => bedwars.deaths as f64,
+                "final_kills" => bedwars.final_kills as f64,
+                "final_deaths" => bedwars.final_deaths as f64,
+                "hours_played" => bedwars.hours_played as f64,
+                "assists" => bedwars.assists as f64,
+                "games_played" => bedwars.games_played() as f64,
+                "final_kills_per_game" => bedwars.final_kills_per_game(),
+                "kills_per_game" => bedwars.kills_per_game(),
+                "wins_per_hour" => bedwars.wins_per_hour(),
+                "assist_ratio" => bedwars.assist_ratio(),
+                _ => return None,
+            },
+            Stats::Skywars(skywars) => match stat {
+                "level" => skywars.level as f64,
+                "winstreak" => skywars.winstreak as f64,
+                "winrate" => skywars.winrate as f64,
+                "kill_death_ratio" => skywars.kill_death_ratio as f64,
+                "wins" => skywars.wins as f64,
+                "losses" => skywars.losses as f64,
+                "kills" => skywars.kills as f64,
+                "deaths" => skywars.deaths as f64,
+                "games_played" => (skywars.wins + skywars.losses) as f64,
+                "kills_per_game" => ratio(skywars.kills, skywars.wins + skywars.losses),
+                _ => return None,
+            },
+            Stats::Duels(duels) => match stat {
+                "winstreak" => duels.winstreak as f64,
+                "best_winstreak" => duels.best_winstreak as f64,
+                "winrate" => duels.winrate as f64,
+                "wins" => duels.wins as f64,
+                "losses" => duels.losses as f64,
+                "games_played" => (duels.wins + duels.losses) as f64,
+                _ => return None,
+            },
+        };
+        Some(value)
+    }
+
+    // Todos os stats do modo, usados no histórico de stats. Os derivados são recalculados.
+    pub fn values(&self) -> BTreeMap<String, f64> {
+        const STATS: [&str; 14] = [
+            "level",
+            "winstreak",
+            "best_winstreak",
+            "winrate",
+            "final_kill_death_ratio",
+            "kill_death_ratio",
+            "wins",
+            "losses",
+            "kills",
+            "deaths",
+            "final_kills",
+            "final_deaths",
+            "hours_played",
+            "assists",
+        ];
+        STATS
+            .iter()
+            .filter_map(|stat| Some((stat.to_string(), self.value(stat)?)))
+            .collect()
+    }
+
+    // Valores das colunas da tela principal, na mesma ordem de `columns`.
+    pub fn column_values(&self, columns: &[ModeColumn]) -> Vec<String> {
+        columns
+            .iter()
+            .map(|column| match self.value(&column.stat) {
+                Some(value) => format!("{:.*}", column.decimals, value),
+                None => String::from("-"),
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Bedwars {
     pub level: i32,
-    pub level_symbol: String,
     pub winstreak: i32,
     pub winrate: f32,
     pub final_kill_death_ratio: f32,
     pub kill_death_ratio: f32,
-    pub level_color: Rgb,
+    // Nível formatado como no jogo, em partes coloridas.
+    pub badge: MinecraftText,
     pub wins: u64,
     pub losses: u64,
     pub kills: u64,
@@ -26,48 +132,317 @@ pub struct Bedwars {
     pub assists: u64,
 }
 
-#[derive(Debug, Clone, Default, PartialEq)]
-pub enum StatsType {
-    #[default]
-    BedwarsAll,
-    BedwarsSolo,
-    BedwarsDoubles,
-    BedwarsTrios,
-    BedwarsQuads,
+// Converte o formato do level_badge em partes coloridas, ex: "§c[§61§e2§a3§b✫§c]". Quando o
+// formato não traz o nível, como em "§7[✫]", ele entra antes do símbolo.
+pub fn parse_level_badge(format: &str, level: i64) -> MinecraftText {
+    // Posição de cada caractere que não é um código de cor (§ ou & e o caractere seguinte).
+    let mut previous = None;
+    let visible_chars: Vec<(usize, char)> = format
+        .char_indices()
+        .filter(|(_, c)| {
+            let is_code = matches!(previous, Some('§') | Some('&')) || matches!(c, '§' | '&');
+            // O caractere depois de § ou & é a cor, mesmo se for outro § ou &.
+            previous = if matches!(previous, Some('§') | Some('&')) {
+                None
+            } else {
+                Some(*c)
+            };
+            !is_code
+        })
+        .collect();
+
+    let mut format = format.to_string();
+    if !visible_chars.iter().any(|(_, c)| c.is_ascii_digit()) {
+        let symbol_position = visible_chars
+            .iter()
+            .find(|(_, c)| !c.is_ascii())
+            .map(|(index, _)| *index);
+        format.insert_str(symbol_position.unwrap_or(format.len()), &level.to_string());
+    }
+
+    MinecraftText::parse(&format, Rgb::new(170, 170, 170))
 }
 
-impl Display for StatsType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+// Stats derivados, calculados a partir dos totais.
+impl Bedwars {
+    // Cada partida termina em vitória ou derrota.
+    pub fn games_played(&self) -> u64 {
+        self.wins + self.losses
+    }
+
+    pub fn final_kills_per_game(&self) -> f64 {
+        ratio(self.final_kills, self.games_played())
+    }
+
+    pub fn kills_per_game(&self) -> f64 {
+        ratio(self.kills, self.games_played())
+    }
+
+    pub fn wins_per_hour(&self) -> f64 {
+        ratio(self.wins, self.hours_played)
+    }
+
+    // Assistências por kill.
+    pub fn assist_ratio(&self) -> f64 {
+        ratio(self.assists, self.kills)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Skywars {
+    pub level: i32,
+    pub winstreak: i32,
+    pub winrate: f32,
+    pub kill_death_ratio: f32,
+    pub wins: u64,
+    pub losses: u64,
+    pub kills: u64,
+    pub deaths: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Duels {
+    pub winstreak: i32,
+    pub best_winstreak: i32,
+    pub winrate: f32,
+    pub wins: u64,
+    pub losses: u64,
+}
+
+// Divisão que retorna 0 em vez de NaN ou infinito quando não há partidas, mortes ou horas.
+pub fn ratio(dividend: u64, divisor: u64) -> f64 {
+    if divisor == 0 {
+        0.
+    } else {
+        dividend as f64 / divisor as f64
+    }
+}
+
+// Coluna extra com um stat derivado e o nome mostrado nas configurações.
+pub struct DerivedColumn {
+    pub name: &'static str,
+    pub column: ModeColumn,
+}
+
+// Colunas extras com stats derivados que o usuário pode ativar nas configurações. Só aparecem
+// nos modos que têm o stat.
+pub fn derived_columns() -> Vec<DerivedColumn> {
+    let column = |name, header: &str, stat: &str, decimals| DerivedColumn {
+        name,
+        column: ModeColumn {
+            header: header.to_string(),
+            stat: stat.to_string(),
+            decimals,
+        },
+    };
+    vec![
+        column("Partidas jogadas", "Partidas", "games_played", 0),
+        column("Final kills por partida", "FK/P", "final_kills_per_game", 2),
+        column("Kills por partida", "K/P", "kills_per_game", 2),
+        column("Vitórias por hora", "V/h", "wins_per_hour", 2),
+        column("Assistências por kill", "Assist/K", "assist_ratio", 2),
+    ]
+}
+
+// Jogo de um modo. Cada jogo tem sua própria estrutura de stats.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Game {
+    Bedwars,
+    Skywars,
+    Duels,
+}
+
+impl Game {
+    // Stats derivados que o jogo consegue calcular.
+    pub fn has_derived_stat(&self, stat: &str) -> bool {
         match self {
-            StatsType::BedwarsAll => write!(f, "Bedwars Geral"),
-            StatsType::BedwarsSolo => write!(f, "Bedwars Solo"),
-            StatsType::BedwarsDoubles => write!(f, "Bedwars Duplas"),
-            StatsType::BedwarsTrios => write!(f, "Bedwars Trios"),
-            StatsType::BedwarsQuads => write!(f, "Bedwars Quartetos"),
+            Game::Bedwars => true,
+            Game::Skywars => matches!(stat, "games_played" | "kills_per_game"),
+            Game::Duels => stat == "games_played",
         }
     }
 }
 
+// Coluna da tela principal: título e nome do stat mostrado.
+#[derive(Debug, Clone)]
+pub struct ModeColumn {
+    pub header: String,
+    pub stat: String,
+    pub decimals: usize,
+}
+
+// Definição de um modo, carregada de assets/modes.json e do arquivo de modos do usuário.
+#[derive(Debug, Clone)]
+pub struct ModeDefinition {
+    pub id: String,
+    pub name: String,
+    pub game: Game,
+    // Objeto dentro de response["stats"] com os stats do modo.
+    pub section: String,
+    // Prefixo das chaves do modo, ex: "solo_" em "solo_wins".
+    pub prefix: String,
+    // Chaves que fogem do padrão prefixo + nome do stat.
+    pub keys: HashMap<String, String>,
+    // Chave de response["stats"]["play_time"] com o tempo jogado no modo.
+    pub play_time: Option<String>,
+    pub columns: Vec<ModeColumn>,
+}
+
+impl ModeDefinition {
+    fn from_json(json: &Value) -> Option<Self> {
+        let game = match json["game"].as_str()? {
+            "bedwars" => Game::Bedwars,
+            "skywars" => Game::Skywars,
+            "duels" => Game::Duels,
+            _ => return None,
+        };
+        let keys = json["keys"]
+            .as_object()
+            .map(|keys| {
+                keys.iter()
+                    .filter_map(|(stat, key)| Some((stat.clone(), key.as_str()?.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let columns = json["columns"]
+            .as_array()?
+            .iter()
+            .filter_map(|column| {
+                Some(ModeColumn {
+                    header: column["header"].as_str()?.to_string(),
+                    stat: column["stat"].as_str()?.to_string(),
+                    decimals: column["decimals"].as_u64().unwrap_or(0) as usize,
+                })
+            })
+            .collect();
+
+        Some(ModeDefinition {
+            id: json["id"].as_str()?.to_string(),
+            name: json["name"].as_str()?.to_string(),
+            game,
+            section: json["section"].as_str()?.to_string(),
+            prefix: json["prefix"].as_str().unwrap_or("").to_string(),
+            keys,
+            play_time: json["play_time"].as_str().map(String::from),
+            columns,
+        })
+    }
+
+    // Chave da API para um stat deste modo.
+    pub fn key(&self, stat: &str) -> String {
+        match self.keys.get(stat) {
+            Some(key) => key.clone(),
+            None => format!("{}{}", self.prefix, stat),
+        }
+    }
+}
+
+// Tabela de modos. Os modos do usuário substituem os padrões com o mesmo id e os novos vão
+// para o final da lista.
+pub fn modes() -> &'static [ModeDefinition] {
+    static MODES: OnceLock<Vec<ModeDefinition>> = OnceLock::new();
+
+    MODES.get_or_init(|| {
+        let default_modes: Value = serde_json::from_str(include_str!("../assets/modes.json"))
+            .expect("assets/modes.json inválido");
+        let mut modes: Vec<ModeDefinition> = default_modes
+            .as_array()
+            .map(|modes| modes.iter().filter_map(ModeDefinition::from_json).collect())
+            .unwrap_or_default();
+
+        let user_modes_path = config::get_modes_file_path();
+        if Path::new(&user_modes_path).exists() {
+            let user_modes = fs::read_to_string(&user_modes_path)
+                .map_err(|e| e.to_string())
+                .and_then(|x| serde_json::from_str::<Value>(&x).map_err(|e| e.to_string()));
+            match user_modes {
+                Ok(Value::Array(user_modes)) => {
+                    for user_mode in user_modes.iter() {
+                        let Some(user_mode) = ModeDefinition::from_json(user_mode) else {
+                            println!("Modo inválido em {user_modes_path}, ignorando.");
+                            continue;
+                        };
+                        match modes.iter_mut().find(|mode| mode.id == user_mode.id) {
+                            Some(mode) => *mode = user_mode,
+                            None => modes.push(user_mode),
+                        }
+                    }
+                }
+                Ok(_) => println!("{user_modes_path} deve conter uma lista de modos."),
+                Err(e) => println!("Falha ao ler {user_modes_path}: {e}"),
+            }
+        }
+
+        assert!(!modes.is_empty(), "Nenhum modo de stats definido.");
+        modes
+    })
+}
+
+// Modo selecionado, identificado pelo id da tabela de modos.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StatsType(String);
+
+impl Default for StatsType {
+    fn default() -> Self {
+        StatsType(modes()[0].id.clone())
+    }
+}
+
+impl Display for StatsType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.definition().name)
+    }
+}
+
 impl StatsType {
+    // Aceita tanto o id quanto o nome do modo.
     pub fn from_string(string: &str) -> Self {
-        match string {
-            "Bedwars Geral" => StatsType::BedwarsAll,
-            "Bedwars Solo" => StatsType::BedwarsSolo,
-            "Bedwars Duplas" => StatsType::BedwarsDoubles,
-            "Bedwars Trios" => StatsType::BedwarsTrios,
-            "Bedwars Quartetos" => StatsType::BedwarsQuads,
-
-            _ => StatsType::BedwarsAll,
+        match modes()
+            .iter()
+            .find(|mode| mode.id == string || mode.name == string)
+        {
+            Some(mode) => StatsType(mode.id.clone()),
+            None => StatsType::default(),
         }
     }
 
+    pub fn id(&self) -> &str {
+        &self.0
+    }
+
     pub fn get_stats_list() -> Vec<StatsType> {
-        vec![
-            StatsType::BedwarsAll,
-            StatsType::BedwarsSolo,
-            StatsType::BedwarsDoubles,
-            StatsType::BedwarsTrios,
-            StatsType::BedwarsQuads,
-        ]
+        modes()
+            .iter()
+            .map(|mode| StatsType(mode.id.clone()))
+            .collect()
+    }
+
+    pub fn definition(&self) -> &'static ModeDefinition {
+        let modes = modes();
+        modes
+            .iter()
+            .find(|mode| mode.id == self.0)
+            .unwrap_or(&modes[0])
+    }
+
+    // Colunas mostradas na tela principal para este modo.
+    pub fn columns(&self) -> &'static [ModeColumn] {
+        &self.definition().columns
+    }
+
+    // Colunas do modo seguidas das colunas extras ativadas que o modo suporta.
+    pub fn visible_columns(&self, extra_columns: &[String]) -> Vec<ModeColumn> {
+        let definition = self.definition();
+        let mut columns = self.columns().to_vec();
+        columns.extend(
+            derived_columns()
+                .into_iter()
+                .map(|derived| derived.column)
+                .filter(|column| {
+                    extra_columns.contains(&column.stat)
+                        && definition.game.has_derived_stat(&column.stat)
+                }),
+        );
+        columns
     }
 }
@@ -2,14 +2,20 @@ use std::{borrow::Borrow, ops::RangeInclusive};
 
 use iced::{
     border::Radius,
+    font::{Style, Weight},
     widget::{
         slider::{Handle, HandleShape, Rail},
-        PickList, TextInput,
+        tooltip::Position,
+        PickList, Row, Text, TextInput,
     },
-    Background, Border, Color, Renderer, Shadow, Theme,
+    Background, Border, Color, Font, Renderer, Shadow, Theme,
 };
+use rand::Rng;
 
-use crate::Message;
+use crate::{
+    minecraft_text::{MinecraftText, TextStyle},
+    Message,
+};
 
 enum Colors {
     WidgetBackground,
@@ -119,17 +125,45 @@ pub fn text_input<'a, Message>(
 where
     Message: Clone,
 {
-    iced::widget::text_input(placeholder, value).style(|_, _| iced::widget::text_input::Style {
-        background: Background::Color(Colors::WidgetBackground.get()),
-        border: Border {
-            color: Colors::WidgetBackground.get(),
-            width: 0.,
-            radius: Radius::new(10),
-        },
-        icon: Colors::GrayTextColor.get(),
-        placeholder: Colors::GrayTextColor.get(),
-        value: Colors::TextColor.get(),
-        selection: Colors::ButtonColor.get(),
+    styled_text_input(placeholder, value, false)
+}
+
+// Campo com um valor que não foi aceito, com a borda vermelha.
+pub fn invalid_text_input<'a, Message>(
+    placeholder: &str,
+    value: &str,
+) -> TextInput<'a, Message, Theme, Renderer>
+where
+    Message: Clone,
+{
+    styled_text_input(placeholder, value, true)
+}
+
+fn styled_text_input<'a, Message>(
+    placeholder: &str,
+    value: &str,
+    invalid: bool,
+) -> TextInput<'a, Message, Theme, Renderer>
+where
+    Message: Clone,
+{
+    let (border_color, border_width) = match invalid {
+        true => (Color::from_rgb8(237, 135, 150), 1.),
+        false => (Colors::WidgetBackground.get(), 0.),
+    };
+    iced::widget::text_input(placeholder, value).style(move |_, _| {
+        iced::widget::text_input::Style {
+            background: Background::Color(Colors::WidgetBackground.get()),
+            border: Border {
+                color: border_color,
+                width: border_width,
+                radius: Radius::new(10),
+            },
+            icon: Colors::GrayTextColor.get(),
+            placeholder: Colors::GrayTextColor.get(),
+            value: Colors::TextColor.get(),
+            selection: Colors::ButtonColor.get(),
+        }
     })
 }
 
@@ -177,3 +211,83 @@ where
         },
     })
 }
+
+pub fn tooltip<'a>(
+    content: impl Into<iced::Element<'a, Message, Theme, Renderer>>,
+    tooltip: impl Into<iced::Element<'a, Message, Theme, Renderer>>,
+) -> iced::widget::Tooltip<'a, Message, Theme, Renderer> {
+    let tooltip =
+        iced::widget::container(tooltip)
+            .padding(8)
+            .style(|_| iced::widget::container::Style {
+                background: Some(Background::Color(Colors::WidgetBackground.get())),
+                text_color: Some(Colors::TextColor.get()),
+                border: Border {
+                    color: Colors::WidgetBackground.get(),
+                    width: 0.,
+                    radius: Radius::new(10),
+                },
+                ..Default::default()
+            });
+    iced::widget::tooltip(content, tooltip, Position::Bottom)
+}
+
+// Texto com cores e estilos do Minecraft. Símbolos fora do ASCII usam a fonte de símbolos e o
+// texto ofuscado (§k) muda de caracteres a cada redesenho, como no jogo. Sublinhado e riscado
+// não são suportados pelo widget de texto e são ignorados.
+pub fn minecraft_text<'a>(minecraft_text: &MinecraftText) -> Row<'a, Message, Theme, Renderer> {
+    let mut row = Row::new();
+    for span in &minecraft_text.spans {
+        let content: String = if span.style.obfuscated {
+            let mut rng = rand::thread_rng();
+            span.text
+                .chars()
+                .map(|c| match c {
+                    ' ' => ' ',
+                    _ => rng.gen_range('a'..='z'),
+                })
+                .collect()
+        } else {
+            span.text.clone()
+        };
+
+        let mut run = String::new();
+        let mut run_is_symbol = false;
+        for c in content.chars() {
+            if !run.is_empty() && c.is_ascii() == run_is_symbol {
+                row = row.push(styled_text(
+                    std::mem::take(&mut run),
+                    run_is_symbol,
+                    &span.style,
+                ));
+            }
+            run_is_symbol = !c.is_ascii();
+            run.push(c);
+        }
+        if !run.is_empty() {
+            row = row.push(styled_text(run, run_is_symbol, &span.style));
+        }
+    }
+    row
+}
+
+fn styled_text<'a>(
+    content: String,
+    is_symbol: bool,
+    style: &TextStyle,
+) -> Text<'a, Theme, Renderer> {
+    let mut font = if is_symbol {
+        Font::with_name("Noto Sans Symbols 2")
+    } else {
+        Font::with_name("Manrope")
+    };
+    if style.bold {
+        font.weight = Weight::Bold;
+    }
+    if style.italic {
+        font.style = Style::Italic;
+    }
+    iced::widget::text(content)
+        .font(font)
+        .color(style.color.to_color())
+}
@@ -0,0 +1,76 @@
+// Índice de ameaça: um número só que resume o quão perigoso um jogador é.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{color_tiers, stats::Stats, util::Rgb};
+
+// Pesos editáveis pelo usuário.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThreatWeight {
+    Level,
+    Fkdr,
+    Wlr,
+    Winstreak,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThreatSettings {
+    pub level_weight: f64,
+    pub fkdr_weight: f64,
+    pub wlr_weight: f64,
+    pub winstreak_weight: f64,
+    pub sort_by_threat: bool,
+    // 0 desativa o alerta.
+    pub alert_threshold: f64,
+}
+
+impl Default for ThreatSettings {
+    fn default() -> Self {
+        ThreatSettings {
+            level_weight: 1.,
+            fkdr_weight: 1.,
+            wlr_weight: 0.5,
+            winstreak_weight: 0.5,
+            sort_by_threat: false,
+            alert_threshold: 5000.,
+        }
+    }
+}
+
+impl ThreatSettings {
+    pub fn set_weight(&mut self, weight: ThreatWeight, value: f64) {
+        match weight {
+            ThreatWeight::Level => self.level_weight = value,
+            ThreatWeight::Fkdr => self.fkdr_weight = value,
+            ThreatWeight::Wlr => self.wlr_weight = value,
+            ThreatWeight::Winstreak => self.winstreak_weight = value,
+        }
+    }
+
+    // Índice = nível^a × FKDR^2b × (1 + WLR)^c × (1 + WS/10)^d. Com os pesos padrão é o
+    // clássico nível × FKDR², ajustado pela taxa de vitória e winstreak. Modos sem FKDR usam o KDR,
+    // modos sem nenhum dos dois (Duels) usam o WLR no lugar e modos sem nível ignoram esse fator.
+    pub fn score(&self, stats: &Stats) -> f64 {
+        let level = stats.value("level").filter(|x| *x > 0.).unwrap_or(1.);
+        let wlr = stats.value("winrate").unwrap_or(0.);
+        let fkdr = stats
+            .value("final_kill_death_ratio")
+            .or(stats.value("kill_death_ratio"))
+            .unwrap_or(wlr);
+        let winstreak = stats.value("winstreak").unwrap_or(0.);
+
+        level.powf(self.level_weight)
+            * fkdr.powf(2. * self.fkdr_weight)
+            * (1. + wlr).powf(self.wlr_weight)
+            * (1. + winstreak / 10.).powf(self.winstreak_weight)
+    }
+
+    pub fn is_alert(&self, score: f64) -> bool {
+        self.alert_threshold > 0. && score >= self.alert_threshold
+    }
+}
+
+pub fn score_color(score: f64) -> Rgb {
+    color_tiers::tier_color(score, &[100., 500., 1500., 5000., 15000.])
+}
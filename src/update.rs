@@ -2,15 +2,15 @@ use std::{
     env,
     fs::{self, File},
     io::Write,
+    time::Duration,
 };
 
-use reqwest::header::{HeaderValue, USER_AGENT};
+use reqwest::Client;
 use serde_json::Value;
 
-pub async fn check_updates() -> Result<String, String> {
-    let last_release_request = match reqwest::Client::new()
+pub async fn check_updates(client: Client) -> Result<String, String> {
+    let last_release_request = match client
         .get("https://api.github.com/repos/jafkc2/KC-Overlay/releases/latest")
-        .header(USER_AGENT, HeaderValue::from_static("KC-Overlay"))
         .send()
         .await
     {
@@ -70,7 +70,7 @@ pub async fn check_updates() -> Result<String, String> {
     }
 }
 
-pub async fn install_update(url: String) -> Result<(), String> {
+pub async fn install_update(client: Client, url: String) -> Result<(), String> {
     let exec_path = env::current_exe().unwrap();
     let mut exec_file = File::create(exec_path.with_extension("new")).unwrap();
 
@@ -85,7 +85,12 @@ pub async fn install_update(url: String) -> Result<(), String> {
     }
 
     println!("{url}");
-    let download = reqwest::get(url).await;
+    // O download do executável pode demorar bem mais que uma consulta comum.
+    let download = client
+        .get(url)
+        .timeout(Duration::from_secs(300))
+        .send()
+        .await;
 
     match download {
         Ok(ok) => {
@@ -1,6 +1,10 @@
 // Código para funções essenciais
 
-use std::{fs::File, io::Read, path::Path, time::Duration};
+use std::{
+    fs::{self, File},
+    io::Write,
+    time::Duration,
+};
 
 use chrono::DateTime;
 use iced::Color;
@@ -39,13 +43,23 @@ pub async fn wait(time: Duration) {
     sleep(time).await;
 }
 
-pub fn get_json(jpathstring: String) -> Value {
-    let jsonpath = Path::new(&jpathstring);
+// Executa leituras e escritas de disco fora da thread da interface.
+pub async fn blocking<T: Send + 'static>(work: impl FnOnce() -> T + Send + 'static) -> T {
+    tokio::task::spawn_blocking(work).await.unwrap()
+}
+
+pub fn get_json(path: &str) -> Result<Value, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
 
-    let mut file = File::open(jsonpath).unwrap();
-    let mut fcontent = String::new();
-    file.read_to_string(&mut fcontent).unwrap();
-    serde_json::from_str(&fcontent).unwrap()
+// Escreve em um arquivo temporário e renomeia, assim o arquivo nunca fica escrito pela metade.
+pub fn write_atomic(path: &str, content: &str) -> std::io::Result<()> {
+    let temp_path = format!("{path}.tmp");
+    let mut file = File::create(&temp_path)?;
+    file.write_all(content.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&temp_path, path)
 }
 
 pub fn lunar_get_newer_logs_path() -> String {
@@ -61,7 +75,7 @@ pub fn lunar_get_newer_logs_path() -> String {
     format!("{}/offline/multiver/logs/latest.log", lunar_dir)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Rgb {
     red: u8,
     green: u8,
@@ -76,14 +90,24 @@ impl Rgb {
             blue: b,
         }
     }
-    pub fn from_hex(hex: &str) -> Self {
-        let hex = hex.replace("#", "");
-
-        let red = u8::from_str_radix(&hex[0..2], 16).unwrap();
-        let green: u8 = u8::from_str_radix(&hex[2..4], 16).unwrap();
-        let blue = u8::from_str_radix(&hex[4..6], 16).unwrap();
-
-        Rgb { red, green, blue }
+    // Aceita "#rrggbb", "rrggbb", "#rgb" e "rgb". Retorna None se a cor for inválida.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim().trim_start_matches('#');
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let hex = match hex.len() {
+            3 => hex.chars().flat_map(|c| [c, c]).collect(),
+            6 => hex.to_string(),
+            _ => return None,
+        };
+
+        let channel = |range: std::ops::Range<usize>| u8::from_str_radix(&hex[range], 16).ok();
+        Some(Rgb {
+            red: channel(0..2)?,
+            green: channel(2..4)?,
+            blue: channel(4..6)?,
+        })
     }
 
     pub fn from_minecraft_color(color_char: &char) -> Self {
@@ -187,3 +211,15 @@ pub fn unix_time_to_date(time: i64) -> String {
         .with_timezone(&chrono::FixedOffset::east_opt(-3 * 3600).unwrap());
     date_time.format("%d/%m/%y às %H:%M").to_string()
 }
+
+// Tempo desde uma data em unix time (ms), ex: "há 3 dias".
+pub fn time_ago(time: i64) -> String {
+    let seconds = (chrono::Utc::now().timestamp_millis() - time).max(0) / 1000;
+    match seconds {
+        s if s < 60 => String::from("agora"),
+        s if s < 3600 => format!("há {} min", s / 60),
+        s if s < 86400 => format!("há {} h", s / 3600),
+        s if s < 2 * 86400 => String::from("há 1 dia"),
+        s => format!("há {} dias", s / 86400),
+    }
+}